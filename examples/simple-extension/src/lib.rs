@@ -65,7 +65,7 @@ fn rust_ext(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
             .downcast::<PyArray1<f64>>()
             .unwrap();
 
-        x.readonly().as_array().sum()
+        x.readonly().unwrap().as_array().sum()
     }
 
     Ok(())
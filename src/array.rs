@@ -1,7 +1,9 @@
 //! Safe interface for NumPy ndarray
 use std::{
+    convert::TryFrom,
     marker::PhantomData,
     mem,
+    ops::Deref,
     os::raw::{c_int, c_void},
     ptr, slice,
 };
@@ -13,16 +15,20 @@ use ndarray::{
 };
 use num_traits::AsPrimitive;
 use pyo3::{
-    ffi, pyobject_native_type_named, type_object, types::PyModule, AsPyPointer, FromPyObject,
-    IntoPy, Py, PyAny, PyDowncastError, PyErr, PyNativeType, PyObject, PyResult, PyTypeInfo,
-    Python, ToPyObject,
+    exceptions::PyTypeError,
+    ffi, pyobject_native_type_named, type_object,
+    types::{PyModule, PyString},
+    AsPyPointer, FromPyObject, IntoPy, Py, PyAny, PyClass, PyClassInitializer, PyDowncastError,
+    PyErr, PyNativeType, PyObject, PyResult, PyTypeInfo, Python, ToPyObject,
 };
 
 use crate::convert::{ArrayExt, IntoPyArray, NpyIndex, ToNpyDims, ToPyArray};
 use crate::dtype::Element;
-use crate::error::{DimensionalityError, FromVecError, NotContiguousError, TypeError};
+use crate::error::{
+    AsSliceError, DimensionalityError, FromVecError, NotAlignedError, NotContiguousError, TypeError,
+};
 use crate::npyffi::{self, npy_intp, NPY_ORDER, PY_ARRAY_API};
-use crate::slice_container::PySliceContainer;
+use crate::slice_container::{PySliceContainer, SliceOwner};
 
 /// A safe, static-typed interface for
 /// [NumPy ndarray](https://numpy.org/doc/stable/reference/arrays.ndarray.html).
@@ -80,7 +86,7 @@ use crate::slice_container::PySliceContainer;
 ///     let pyarray = PyArray::arange(py, 0., 4., 1.).reshape([2, 2]).unwrap();
 ///     let array = array![[3., 4.], [5., 6.]];
 ///     assert_eq!(
-///         array.dot(&pyarray.readonly().as_array()),
+///         array.dot(&pyarray.readonly().unwrap().as_array()),
 ///         array![[8., 15.], [12., 23.]]
 ///     );
 /// });
@@ -167,12 +173,71 @@ impl<'py, T: Element, D: Dimension> FromPyObject<'py> for &'py PyArray<T, D> {
     }
 }
 
+impl<T: Element, D: Dimension> PyArray<T, D> {
+    /// Extracts an array like its `FromPyObject` implementation, but rejects instances of
+    /// subclasses of `numpy.ndarray` like `np.matrix` or `np.ma.MaskedArray`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray2;
+    /// use pyo3::types::IntoPyDict;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+    ///     let matrix = py.eval("np.matrix([[1.0, 2.0]])", None, Some(locals)).unwrap();
+    ///     assert!(matrix.extract::<&PyArray2<f64>>().is_ok());
+    ///     assert!(PyArray2::<f64>::extract_exact(matrix).is_err());
+    /// });
+    /// ```
+    pub fn extract_exact(ob: &PyAny) -> PyResult<&Self> {
+        if unsafe { npyffi::PyArray_CheckExact(ob.as_ptr()) } == 0 {
+            return Err(PyDowncastError::new(ob, "numpy.ndarray").into());
+        }
+        ob.extract()
+    }
+
+    /// Wraps this array into the type of `like` using its `__array_wrap__` method, so that results
+    /// computed from instances of subclasses of `numpy.ndarray` keep their type and metadata,
+    /// e.g. the units of an astropy `Quantity`.
+    ///
+    /// Returns this array itself if `like` is an exact `numpy.ndarray` or does not
+    /// implement `__array_wrap__`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray2;
+    /// use pyo3::types::IntoPyDict;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+    ///     let matrix = py.eval("np.matrix([[1.0, 2.0]])", None, Some(locals)).unwrap();
+    ///     let input: &PyArray2<f64> = matrix.extract().unwrap();
+    ///     let doubled = PyArray2::from_array(py, &(input.to_owned_array() * 2.0));
+    ///     assert!(doubled.is_exact_ndarray());
+    ///     let result = doubled.wrap_like(matrix).unwrap();
+    ///     pyo3::py_run!(py, result, "import numpy as np; assert type(result) is np.matrix");
+    /// });
+    /// ```
+    pub fn wrap_like<'py>(&'py self, like: &'py PyAny) -> PyResult<&'py PyAny> {
+        if unsafe { npyffi::PyArray_CheckExact(like.as_ptr()) } != 0
+            || !like.hasattr("__array_wrap__")?
+        {
+            return Ok(self.as_ref());
+        }
+        like.call_method1("__array_wrap__", (self,))
+    }
+}
+
 impl<T, D> PyArray<T, D> {
     /// Gets a raw [`PyArrayObject`](../npyffi/objects/struct.PyArrayObject.html) pointer.
     pub fn as_array_ptr(&self) -> *mut npyffi::PyArrayObject {
         self.as_ptr() as _
     }
 
+    /// Returns `true` if this is an instance of exactly `numpy.ndarray`
+    /// and not of a subclass like `np.matrix` or `np.ma.MaskedArray`.
+    pub fn is_exact_ndarray(&self) -> bool {
+        unsafe { npyffi::PyArray_CheckExact(self.as_ptr()) != 0 }
+    }
+
     /// Returns `dtype` of the array.
     /// Counterpart of `array.dtype` in Python.
     ///
@@ -200,8 +265,11 @@ impl<T, D> PyArray<T, D> {
     }
 
     /// Returns a temporally unwriteable reference of the array.
-    pub fn readonly(&self) -> crate::PyReadonlyArray<T, D> {
-        self.into()
+    ///
+    /// Returns an error if the array is not [aligned](#method.is_aligned),
+    /// such arrays can be accessed via [`as_unaligned`](#method.as_unaligned) instead.
+    pub fn readonly(&self) -> Result<crate::PyReadonlyArray<T, D>, NotAlignedError> {
+        crate::PyReadonlyArray::try_from(self)
     }
 
     /// Returns `true` if the internal data of the array is C-style contiguous
@@ -237,6 +305,47 @@ impl<T, D> PyArray<T, D> {
         self.check_flag(npyffi::NPY_ARRAY_C_CONTIGUOUS)
     }
 
+    /// Returns `true` if the data pointer and the strides of the array are multiples of
+    /// the alignment of `T`, so that references to its elements can be formed.
+    ///
+    /// Arrays can be unaligned when they are e.g. fields of packed structured arrays or
+    /// created by `np.frombuffer` at odd offsets. Such arrays cannot be viewed as slices or
+    /// `ArrayView`s, but can be accessed via [`as_unaligned`](#method.as_unaligned).
+    ///
+    /// # Example
+    /// ```
+    /// use pyo3::types::IntoPyDict;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = numpy::PyArray::<f64, _>::zeros(py, [4], false);
+    ///     assert!(array.is_aligned());
+    ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+    ///     let unaligned: &numpy::PyArray1<f64> = py
+    ///         .eval("np.frombuffer(bytes(17), dtype='float64', offset=1)", Some(locals), None)
+    ///         .unwrap()
+    ///         .downcast()
+    ///         .unwrap();
+    ///     assert!(!unaligned.is_aligned());
+    /// });
+    /// ```
+    pub fn is_aligned(&self) -> bool {
+        let align = mem::align_of::<T>();
+        let data = unsafe { self.data() } as usize;
+        data % align == 0
+            && self
+                .shape()
+                .iter()
+                .zip(self.strides())
+                .all(|(&len, &stride)| len <= 1 || stride % align as isize == 0)
+    }
+
+    pub(crate) fn check_aligned(&self) -> Result<(), NotAlignedError> {
+        if self.is_aligned() {
+            Ok(())
+        } else {
+            Err(NotAlignedError::new(mem::align_of::<T>()))
+        }
+    }
+
     /// Get `Py<PyArray>` from `&PyArray`, which is the owned wrapper of PyObject.
     ///
     /// You can use this method when you have to avoid lifetime annotation to your function args
@@ -250,7 +359,7 @@ impl<T, D> PyArray<T, D> {
     /// }
     /// let array = return_py_array();
     /// pyo3::Python::with_gil(|py| {
-    ///     assert_eq!(array.as_ref(py).readonly().as_slice().unwrap(), &[0, 0, 0, 0, 0]);
+    ///     assert_eq!(array.as_ref(py).readonly().unwrap().as_slice().unwrap(), &[0, 0, 0, 0, 0]);
     /// });
     /// ```
     pub fn to_owned(&self) -> Py<Self> {
@@ -478,6 +587,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
         dims: ID,
         strides: *const npy_intp,
         data_ptr: *const T,
+        flag: c_int,
         container: *mut PyAny,
     ) -> &'py Self
     where
@@ -489,10 +599,10 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
             T::get_dtype(py).into_dtype_ptr(),
             dims.ndim_cint(),
             dims.as_dims_ptr(),
-            strides as *mut npy_intp,    // strides
-            data_ptr as *mut c_void,     // data
-            npyffi::NPY_ARRAY_WRITEABLE, // flag
-            ptr::null_mut(),             // obj
+            strides as *mut npy_intp, // strides
+            data_ptr as *mut c_void,  // data
+            flag,                     // flag
+            ptr::null_mut(),          // obj
         );
 
         PY_ARRAY_API.PyArray_SetBaseObject(
@@ -510,6 +620,28 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
         data_ptr: *const T,
         container: C,
     ) -> &'py Self
+    where
+        ID: IntoDimension<Dim = D>,
+        PySliceContainer: From<C>,
+    {
+        Self::from_raw_parts_with_flag(
+            py,
+            dims,
+            strides,
+            data_ptr,
+            npyffi::NPY_ARRAY_WRITEABLE,
+            container,
+        )
+    }
+
+    pub(crate) unsafe fn from_raw_parts_with_flag<'py, ID, C>(
+        py: Python<'py>,
+        dims: ID,
+        strides: *const npy_intp,
+        data_ptr: *const T,
+        flag: c_int,
+        container: C,
+    ) -> &'py Self
     where
         ID: IntoDimension<Dim = D>,
         PySliceContainer: From<C>,
@@ -518,7 +650,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
             .create_cell(py)
             .expect("Object creation failed.");
 
-        Self::new_with_data(py, dims, strides, data_ptr, container as *mut PyAny)
+        Self::new_with_data(py, dims, strides, data_ptr, flag, container as *mut PyAny)
     }
 
     /// Creates a NumPy array backed by `array` and ties its ownership to the Python object `container`.
@@ -570,6 +702,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
             dims,
             strides.as_ptr(),
             data_ptr,
+            npyffi::NPY_ARRAY_WRITEABLE,
             container as *const PyAny as *mut PyAny,
         )
     }
@@ -590,7 +723,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// use numpy::PyArray2;
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray: &PyArray2<usize> = PyArray2::zeros(py, [2, 2], false);
-    ///     assert_eq!(pyarray.readonly().as_array(), array![[0, 0], [0, 0]]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_array(), array![[0, 0], [0, 0]]);
     /// });
     /// ```
     pub fn zeros<ID>(py: Python, dims: ID, is_fortran: bool) -> &Self
@@ -616,10 +749,13 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// # Safety
     /// If the internal array is not readonly and can be mutated from Python code,
     /// holding the slice might cause undefined behavior.
-    pub unsafe fn as_slice(&self) -> Result<&[T], NotContiguousError> {
+    ///
+    /// Returns an error if the array is not contiguous or not [aligned](#method.is_aligned).
+    pub unsafe fn as_slice(&self) -> Result<&[T], AsSliceError> {
         if !self.is_contiguous() {
-            Err(NotContiguousError)
+            Err(NotContiguousError.into())
         } else {
+            self.check_aligned()?;
             Ok(slice::from_raw_parts(self.data(), self.len()))
         }
     }
@@ -629,10 +765,13 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// # Safety
     /// If another reference to the internal data exists(e.g., `&[T]` or `ArrayView`),
     /// it might cause undefined behavior.
-    pub unsafe fn as_slice_mut(&self) -> Result<&mut [T], NotContiguousError> {
+    ///
+    /// Returns an error if the array is not contiguous or not [aligned](#method.is_aligned).
+    pub unsafe fn as_slice_mut(&self) -> Result<&mut [T], AsSliceError> {
         if !self.is_contiguous() {
-            Err(NotContiguousError)
+            Err(NotContiguousError.into())
         } else {
+            self.check_aligned()?;
             Ok(slice::from_raw_parts_mut(self.data(), self.len()))
         }
     }
@@ -649,7 +788,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     ///
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_owned_array(py, array![[1, 2], [3, 4]]);
-    ///     assert_eq!(pyarray.readonly().as_array(), array![[1, 2], [3, 4]]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_array(), array![[1, 2], [3, 4]]);
     /// });
     /// ```
     pub fn from_owned_array<'py>(py: Python<'py>, arr: Array<T, D>) -> &'py Self {
@@ -675,6 +814,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// # Safety
     /// If the internal array is not readonly and can be mutated from Python code,
     /// holding the slice might cause undefined behavior.
+    /// The array must also be [aligned](#method.is_aligned).
     #[inline(always)]
     pub unsafe fn get(&self, index: impl NpyIndex<Dim = D>) -> Option<&T> {
         let offset = index.get_checked::<T>(self.shape(), self.strides())?;
@@ -688,8 +828,8 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     ///
     /// # Safety
     ///
-    /// Passing an invalid index is undefined behavior. The element must also have been initialized.
-    /// The elemet must also not be modified by Python code.
+    /// Passing an invalid index is undefined behavior. The element must also have been initialized
+    /// and be [aligned](#method.is_aligned). The elemet must also not be modified by Python code.
     ///
     /// # Example
     /// ```
@@ -712,8 +852,8 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     ///
     /// # Safety
     ///
-    /// Passing an invalid index is undefined behavior. The element must also have been initialized.
-    /// The element must also not be accessed by Python code.
+    /// Passing an invalid index is undefined behavior. The element must also have been initialized
+    /// and be [aligned](#method.is_aligned). The element must also not be accessed by Python code.
     #[inline(always)]
     #[allow(clippy::mut_from_ref)]
     pub unsafe fn uget_mut<Idx>(&self, index: Idx) -> &mut T
@@ -778,7 +918,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     ///     assert_eq!(array.to_vec().unwrap(), vec![0, 1, 2, 3]);
     /// });
     /// ```
-    pub fn to_vec(&self) -> Result<Vec<T>, NotContiguousError> {
+    pub fn to_vec(&self) -> Result<Vec<T>, AsSliceError> {
         unsafe { self.as_slice() }.map(ToOwned::to_owned)
     }
 
@@ -793,7 +933,7 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// use numpy::PyArray;
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_array(py, &array![[1, 2], [3, 4]]);
-    ///     assert_eq!(pyarray.readonly().as_array(), array![[1, 2], [3, 4]]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_array(), array![[1, 2], [3, 4]]);
     /// });
     /// ```
     pub fn from_array<'py, S>(py: Python<'py>, arr: &ArrayBase<S, D>) -> &'py Self
@@ -813,7 +953,10 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// # Safety
     /// If the internal array is not readonly and can be mutated from Python code,
     /// holding the `ArrayView` might cause undefined behavior.
+    ///
+    /// The array must also be [aligned](#method.is_aligned).
     pub unsafe fn as_array(&self) -> ArrayView<'_, T, D> {
+        debug_assert!(self.is_aligned());
         let (shape, ptr, inverted_axises) = self.ndarray_shape_ptr();
         let mut res = ArrayView::from_shape_ptr(shape, ptr);
         inverted_axises.invert(&mut res);
@@ -825,7 +968,10 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     /// # Safety
     /// If another reference to the internal data exists(e.g., `&[T]` or `ArrayView`),
     /// it might cause undefined behavior.
+    ///
+    /// The array must also be [aligned](#method.is_aligned).
     pub unsafe fn as_array_mut(&self) -> ArrayViewMut<'_, T, D> {
+        debug_assert!(self.is_aligned());
         let (shape, ptr, inverted_axises) = self.ndarray_shape_ptr();
         let mut res = ArrayViewMut::from_shape_ptr(shape, ptr);
         inverted_axises.invert(&mut res);
@@ -863,8 +1009,37 @@ impl<T: Element, D: Dimension> PyArray<T, D> {
     ///     )
     /// });
     /// ```
+    ///
+    /// Arrays which are not [aligned](#method.is_aligned) are copied using unaligned loads
+    /// like [`to_owned_array_unaligned`](#method.to_owned_array_unaligned) does.
     pub fn to_owned_array(&self) -> Array<T, D> {
-        unsafe { self.as_array() }.to_owned()
+        if self.is_aligned() {
+            return unsafe { self.as_array() }.to_owned();
+        }
+        let (shape, strides) = (self.shape(), self.strides());
+        let data = unsafe { self.data() } as *const u8;
+        let mut index = vec![0; shape.len()];
+        let mut elements = Vec::with_capacity(self.len());
+        for _ in 0..self.len() {
+            let offset = index
+                .iter()
+                .zip(strides)
+                .map(|(&i, &stride)| i as isize * stride)
+                .sum::<isize>();
+            // The element is cloned instead of moved out, so the bitwise copy must not be dropped.
+            let element = mem::ManuallyDrop::new(unsafe {
+                ptr::read_unaligned(data.offset(offset) as *const T)
+            });
+            elements.push(T::clone(&element));
+            for (i, &len) in index.iter_mut().zip(shape).rev() {
+                *i += 1;
+                if *i < len {
+                    break;
+                }
+                *i = 0;
+            }
+        }
+        Array::from_shape_vec(self.dims(), elements).unwrap()
     }
 }
 
@@ -900,7 +1075,7 @@ impl<D: Dimension> PyArray<PyObject, D> {
     ///
     ///     let pyarray = PyArray::from_owned_object_array(py, array);
     ///
-    ///     assert!(pyarray.readonly().get(0).unwrap().as_ref(py).is_instance::<CustomElement>().unwrap());
+    ///     assert!(pyarray.readonly().unwrap().get(0).unwrap().as_ref(py).is_instance::<CustomElement>().unwrap());
     /// });
     /// ```
     pub fn from_owned_object_array<'py, T>(py: Python<'py>, arr: Array<Py<T>, D>) -> &'py Self {
@@ -908,6 +1083,167 @@ impl<D: Dimension> PyArray<PyObject, D> {
         let data_ptr = arr.as_ptr() as *const PyObject;
         unsafe { PyArray::from_raw_parts(py, dims, strides.as_ptr(), data_ptr, arr) }
     }
+
+    /// Constructs a `PyArray` containing objects by moving each element of `arr`
+    /// into a new instance of the Python class `T`.
+    ///
+    /// Use [`downcast_elements`](#method.downcast_elements) to access the elements as `Py<T>` again.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use pyo3::{pyclass, Python};
+    /// use numpy::PyArray;
+    ///
+    /// #[pyclass]
+    /// struct CustomElement {
+    ///     foo: i32,
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let array = array![CustomElement { foo: 1 }, CustomElement { foo: 2 }];
+    ///     let pyarray = PyArray::from_pyclass_array(py, array).unwrap();
+    ///     let elements = pyarray.readonly().unwrap();
+    ///     let elements = elements.downcast_elements::<CustomElement>().unwrap();
+    ///     assert_eq!(elements[1].borrow(py).foo, 2);
+    /// });
+    /// ```
+    pub fn from_pyclass_array<'py, T>(py: Python<'py>, arr: Array<T, D>) -> PyResult<&'py Self>
+    where
+        T: PyClass + Into<PyClassInitializer<T>>,
+    {
+        let dim = arr.raw_dim();
+        // The addresses of the elements in logical order locate them within the storage of `arr`.
+        let addresses = arr
+            .iter()
+            .map(|element| element as *const T as usize)
+            .collect::<Vec<_>>();
+        let storage = arr.into_raw_vec();
+        let start = storage.as_ptr() as usize;
+        let mut storage = storage.into_iter().map(Some).collect::<Vec<_>>();
+        let mut objects = Vec::with_capacity(addresses.len());
+        for (i, address) in addresses.into_iter().enumerate() {
+            let index = match mem::size_of::<T>() {
+                0 => i,
+                size => (address - start) / size,
+            };
+            let element = storage[index]
+                .take()
+                .expect("PyArray::from_pyclass_array: every element is moved exactly once");
+            objects.push(Py::new(py, element)?);
+        }
+        let objects = Array::from_shape_vec(dim, objects)
+            .expect("PyArray::from_pyclass_array: shape and length of objects must match");
+        Ok(Self::from_owned_object_array(py, objects))
+    }
+
+    /// Checks that every element of this array is an instance of the Python class `T` and
+    /// returns the elements as [`ndarray::ArrayView`] of `Py<T>`.
+    ///
+    /// Each element is checked exactly once, so afterwards they can be borrowed without
+    /// further downcasts. Returns `TypeError` if any element is not an instance of `T`.
+    ///
+    /// Please consider the use of the safe alternative
+    /// [`PyReadonlyArray::downcast_elements`](../struct.PyReadonlyArray.html#method.downcast_elements).
+    ///
+    /// # Safety
+    /// If the internal array is not readonly and can be mutated from Python code,
+    /// holding the `ArrayView` might cause undefined behavior, in particular if elements
+    /// are replaced by objects of other types.
+    pub unsafe fn downcast_elements<T: PyClass>(&self) -> PyResult<ArrayView<'_, Py<T>, D>> {
+        let py = self.py();
+        for element in self.as_array().iter() {
+            let element = element.as_ref(py);
+            if !T::is_type_of(element) {
+                return Err(PyDowncastError::new(element, T::NAME).into());
+            }
+        }
+        // `Py<T>` and `PyObject` are both represented by a pointer to the Python object.
+        let (shape, ptr, inverted_axises) = self.ndarray_shape_ptr();
+        let mut res = ArrayView::from_shape_ptr(shape, ptr as *const Py<T>);
+        inverted_axises.invert(&mut res);
+        Ok(res)
+    }
+
+    /// Constructs a `PyArray` containing Python `str` objects from an array of strings.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use numpy::PyArray;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let pyarray = PyArray::from_strings(py, &array!["foo", "bar"]);
+    ///     pyo3::py_run!(py, pyarray, "assert pyarray.dtype == object and pyarray[1] == 'bar'");
+    /// });
+    /// ```
+    pub fn from_strings<'py, S, A>(py: Python<'py>, arr: &ArrayBase<S, D>) -> &'py Self
+    where
+        S: Data<Elem = A>,
+        A: AsRef<str>,
+    {
+        let objects = arr.map(|s| PyString::new(py, s.as_ref()).to_object(py));
+        Self::from_owned_object_array(py, objects)
+    }
+
+    /// Copies the elements of this array, which must all be Python `str` objects,
+    /// into an [`ndarray::Array`] of `String`s.
+    ///
+    /// Returns `TypeError` if any element is not a `str`, including `None`, see
+    /// [`to_optional_string_array`](#method.to_optional_string_array) for arrays with missing values.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray1;
+    /// use pyo3::types::IntoPyDict;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+    ///     let pyarray: &PyArray1<pyo3::PyObject> = py
+    ///         .eval("np.array(['foo', 'bar'], dtype=object)", Some(locals), None)
+    ///         .unwrap()
+    ///         .extract()
+    ///         .unwrap();
+    ///     assert_eq!(pyarray.to_string_array().unwrap(), ndarray::array!["foo", "bar"]);
+    /// });
+    /// ```
+    pub fn to_string_array(&self) -> PyResult<Array<String, D>> {
+        self.extract_elements(extract_string)
+    }
+
+    /// Copies the elements of this array into an [`ndarray::Array`] of `Option<String>`s
+    /// mapping `None` to `Option::None` like [`to_string_array`](#method.to_string_array).
+    pub fn to_optional_string_array(&self) -> PyResult<Array<Option<String>, D>> {
+        self.extract_elements(|element| {
+            if element.is_none() {
+                Ok(None)
+            } else {
+                extract_string(element).map(Some)
+            }
+        })
+    }
+
+    fn extract_elements<U, F>(&self, mut f: F) -> PyResult<Array<U, D>>
+    where
+        F: FnMut(&PyAny) -> PyResult<U>,
+    {
+        let py = self.py();
+        let view = unsafe { self.as_array() };
+        let mut elements = Vec::with_capacity(view.len());
+        for element in view.iter() {
+            elements.push(f(element.as_ref(py))?);
+        }
+        Ok(Array::from_shape_vec(view.raw_dim(), elements)
+            .expect("PyArray::extract_elements: shape and length of elements must match"))
+    }
+}
+
+fn extract_string(element: &PyAny) -> PyResult<String> {
+    match element.downcast::<PyString>() {
+        Ok(s) => Ok(s.to_str()?.to_owned()),
+        Err(_) => Err(PyTypeError::new_err(format!(
+            "expected an array of str objects, but found an element of type {}",
+            element.get_type().name()?
+        ))),
+    }
 }
 
 impl<T: Copy + Element> PyArray<T, Ix0> {
@@ -928,7 +1264,7 @@ impl<T: Element> PyArray<T, Ix1> {
     /// let array = [1, 2, 3, 4, 5];
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_slice(py, &array);
-    ///     assert_eq!(pyarray.readonly().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
     /// });
     /// ```
     pub fn from_slice<'py>(py: Python<'py>, slice: &[T]) -> &'py Self {
@@ -955,13 +1291,51 @@ impl<T: Element> PyArray<T, Ix1> {
     /// let vec = vec![1, 2, 3, 4, 5];
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_vec(py, vec);
-    ///     assert_eq!(pyarray.readonly().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
     /// });
     /// ```
     pub fn from_vec<'py>(py: Python<'py>, vec: Vec<T>) -> &'py Self {
         IntoPyArray::into_pyarray(vec, py)
     }
 
+    /// Construct a readonly one-dimension PyArray which keeps `owner` alive as its base object.
+    ///
+    /// This does not copy the data. As the data can be shared with other references
+    /// to `owner`, e.g. clones of an `Arc`, the resulting array is not writeable.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use numpy::PyArray;
+    /// let shared: Arc<[f64]> = Arc::from(vec![1.0, 2.0, 3.0]);
+    /// pyo3::Python::with_gil(|py| {
+    ///     let pyarray = PyArray::from_owner(py, shared.clone());
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[1.0, 2.0, 3.0]);
+    ///     pyo3::py_run!(py, pyarray, "assert not pyarray.flags['WRITEABLE']");
+    /// });
+    /// ```
+    pub fn from_owner<'py, O>(py: Python<'py>, owner: O) -> &'py Self
+    where
+        O: Deref + Send + 'static,
+        O::Target: AsRef<[T]>,
+    {
+        let owner = Box::new(owner);
+        let slice = (**owner).as_ref();
+        let dims = [slice.len()];
+        let strides = [mem::size_of::<T>() as npy_intp];
+        let data_ptr = slice.as_ptr();
+        unsafe {
+            PyArray::from_raw_parts_with_flag(
+                py,
+                dims,
+                strides.as_ptr(),
+                data_ptr,
+                0,
+                SliceOwner(owner),
+            )
+        }
+    }
+
     /// Construct one-dimension PyArray from a type which implements
     /// [`ExactSizeIterator`](https://doc.rust-lang.org/std/iter/trait.ExactSizeIterator.html).
     ///
@@ -972,7 +1346,7 @@ impl<T: Element> PyArray<T, Ix1> {
     /// let vec = vec![1, 2, 3, 4, 5];
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_exact_iter(py, vec.iter().map(|&x| x));
-    ///     assert_eq!(pyarray.readonly().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
     /// });
     /// ```
     pub fn from_exact_iter(py: Python<'_>, iter: impl ExactSizeIterator<Item = T>) -> &Self {
@@ -1004,7 +1378,7 @@ impl<T: Element> PyArray<T, Ix1> {
     /// let set: std::collections::BTreeSet<u32> = [4, 3, 2, 5, 1].into_iter().cloned().collect();
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_iter(py, set);
-    ///     assert_eq!(pyarray.readonly().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3, 4, 5]);
     /// });
     /// ```
     pub fn from_iter(py: Python<'_>, iter: impl IntoIterator<Item = T>) -> &Self {
@@ -1094,7 +1468,7 @@ impl<T: Element> PyArray<T, Ix2> {
     /// let vec2 = vec![vec![1, 2, 3]; 2];
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_vec2(py, &vec2).unwrap();
-    ///     assert_eq!(pyarray.readonly().as_array(), array![[1, 2, 3], [1, 2, 3]]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_array(), array![[1, 2, 3], [1, 2, 3]]);
     ///     assert!(PyArray::from_vec2(py, &[vec![1], vec![2, 3]]).is_err());
     /// });
     /// ```
@@ -1130,7 +1504,7 @@ impl<T: Element> PyArray<T, Ix3> {
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::from_vec3(py, &vec3).unwrap();
     ///     assert_eq!(
-    ///         pyarray.readonly().as_array(),
+    ///         pyarray.readonly().unwrap().as_array(),
     ///         array![[[1, 2], [1, 2]], [[1, 2], [1, 2]]]
     ///     );
     ///     assert!(PyArray::from_vec3(py, &[vec![vec![1], vec![]]]).is_err());
@@ -1169,7 +1543,7 @@ impl<T: Element, D> PyArray<T, D> {
     ///     let pyarray_f = PyArray::arange(py, 2.0, 5.0, 1.0);
     ///     let pyarray_i = unsafe { PyArray::<i64, _>::new(py, [3], false) };
     ///     assert!(pyarray_f.copy_to(pyarray_i).is_ok());
-    ///     assert_eq!(pyarray_i.readonly().as_slice().unwrap(), &[2, 3, 4]);
+    ///     assert_eq!(pyarray_i.readonly().unwrap().as_slice().unwrap(), &[2, 3, 4]);
     /// });
     /// ```
     pub fn copy_to<U: Element>(&self, other: &PyArray<U, D>) -> PyResult<()> {
@@ -1191,7 +1565,7 @@ impl<T: Element, D> PyArray<T, D> {
     ///     let pyarray_f = PyArray::arange(py, 2.0, 5.0, 1.0);
     ///     let pyarray_i = pyarray_f.cast::<i32>(false).unwrap();
     ///     assert!(pyarray_f.copy_to(pyarray_i).is_ok());
-    ///     assert_eq!(pyarray_i.readonly().as_slice().unwrap(), &[2, 3, 4]);
+    ///     assert_eq!(pyarray_i.readonly().unwrap().as_slice().unwrap(), &[2, 3, 4]);
     /// });
     /// ```
     pub fn cast<'py, U: Element>(&'py self, is_fortran: bool) -> PyResult<&'py PyArray<U, D>> {
@@ -1223,7 +1597,7 @@ impl<T: Element, D> PyArray<T, D> {
     /// pyo3::Python::with_gil(|py| {
     ///     let array = PyArray::from_exact_iter(py, 0..9);
     ///     let array = array.reshape([3, 3]).unwrap();
-    ///     assert_eq!(array.readonly().as_array(), array![[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+    ///     assert_eq!(array.readonly().unwrap().as_array(), array![[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
     ///     assert!(array.reshape([5]).is_err());
     /// });
     /// ```
@@ -1263,6 +1637,94 @@ impl<T: Element, D> PyArray<T, D> {
     }
 }
 
+impl<T, D: Dimension> PyArray<T, D> {
+    /// Returns a view of the field `name` of this structured array
+    /// which shares its data with `self`.
+    ///
+    /// Fails if there is no such field or its data type is not equivalent to `U`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::{Element, PyArray1, PyArrayDescr};
+    /// use pyo3::Python;
+    ///
+    /// #[derive(Clone)]
+    /// #[repr(C)]
+    /// struct Point {
+    ///     x: f64,
+    ///     y: f64,
+    /// }
+    ///
+    /// unsafe impl Element for Point {
+    ///     const IS_COPY: bool = true;
+    ///
+    ///     fn get_dtype(py: Python) -> &PyArrayDescr {
+    ///         PyArrayDescr::new(py, &vec![("x", "f8"), ("y", "f8")]).unwrap()
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let points = PyArray1::from_vec(py, vec![Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }]);
+    ///     let x = points.field::<f64>("x").unwrap();
+    ///     assert_eq!(x.readonly().unwrap().as_array(), ndarray::array![1.0, 3.0]);
+    ///
+    ///     assert!(points.field::<f64>("z").is_err());
+    ///     assert!(points.field::<i32>("y").is_err());
+    /// });
+    /// ```
+    pub fn field<'py, U: Element>(&'py self, name: &str) -> PyResult<&'py PyArray<U, D>> {
+        let py = self.py();
+        let (descr, offset) = self.field_descr::<U>(name)?;
+        let ptr = unsafe {
+            PY_ARRAY_API.PyArray_GetField(
+                self.as_array_ptr(),
+                descr.into_dtype_ptr(),
+                offset as c_int,
+            )
+        };
+        if ptr.is_null() {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(unsafe { PyArray::<U, D>::from_owned_ptr(py, ptr) })
+        }
+    }
+
+    /// Assigns `values` to the field `name` of this structured array,
+    /// broadcasting them to the shape of `self`.
+    ///
+    /// Fails if there is no such field, its data type is not equivalent to `U`
+    /// or `self` is not writeable.
+    pub fn set_field<U: Element, D2: Dimension>(
+        &self,
+        name: &str,
+        values: &PyArray<U, D2>,
+    ) -> PyResult<()> {
+        let (descr, offset) = self.field_descr::<U>(name)?;
+        let result = unsafe {
+            PY_ARRAY_API.PyArray_SetField(
+                self.as_array_ptr(),
+                descr.into_dtype_ptr(),
+                offset as c_int,
+                values.as_ptr(),
+            )
+        };
+        if result == -1 {
+            Err(PyErr::fetch(self.py()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn field_descr<U: Element>(&self, name: &str) -> PyResult<(&crate::PyArrayDescr, usize)> {
+        let (descr, offset) = self.dtype().get_field(name)?;
+        let expected = U::get_dtype(self.py());
+        if !descr.is_equiv_to(expected) {
+            return Err(TypeError::new(descr, expected).into());
+        }
+        Ok((descr, offset))
+    }
+}
+
 impl<T: Element + AsPrimitive<f64>> PyArray<T, Ix1> {
     /// Return evenly spaced values within a given interval.
     /// Same as [numpy.arange](https://numpy.org/doc/stable/reference/generated/numpy.arange.html).
@@ -1274,9 +1736,9 @@ impl<T: Element + AsPrimitive<f64>> PyArray<T, Ix1> {
     /// use numpy::PyArray;
     /// pyo3::Python::with_gil(|py| {
     ///     let pyarray = PyArray::arange(py, 2.0, 4.0, 0.5);
-    ///     assert_eq!(pyarray.readonly().as_slice().unwrap(), &[2.0, 2.5, 3.0, 3.5]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[2.0, 2.5, 3.0, 3.5]);
     ///     let pyarray = PyArray::arange(py, -2, 4, 3);
-    ///     assert_eq!(pyarray.readonly().as_slice().unwrap(), &[-2, 1]);
+    ///     assert_eq!(pyarray.readonly().unwrap().as_slice().unwrap(), &[-2, 1]);
     /// });
     pub fn arange(py: Python, start: T, stop: T, step: T) -> &Self {
         unsafe {
@@ -0,0 +1,152 @@
+//! Extracting arrays from objects implementing the NumPy array interface
+use std::ops::Deref;
+
+use ndarray::Dimension;
+use pyo3::{exceptions::PyTypeError, ffi, AsPyPointer, FromPyObject, PyAny, PyErr, PyResult};
+
+use crate::dtype::Element;
+use crate::npyffi::{self, PY_ARRAY_API};
+use crate::PyArray;
+
+/// An array extracted from an arbitrary object implementing the
+/// [array interface](https://numpy.org/doc/stable/reference/arrays.interface.html).
+///
+/// Besides NumPy arrays, this accepts objects providing `__array_interface__`,
+/// like many containers of third-party libraries, or an `__array__` method, like pandas
+/// series or xarray data arrays, without having to import the libraries defining them.
+/// The data is shared with the original object whenever the interface allows it.
+///
+/// # Example
+/// ```
+/// use numpy::PyArrayLike1;
+/// use pyo3::types::IntoPyDict;
+/// pyo3::Python::with_gil(|py| {
+///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+///     let obj = py.eval(
+///         "type('Wrapper', (), {'__array__': lambda self, dtype=None: np.arange(3.0)})()",
+///         None,
+///         Some(locals),
+///     ).unwrap();
+///     let array: PyArrayLike1<f64> = obj.extract().unwrap();
+///     assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[0.0, 1.0, 2.0]);
+/// });
+/// ```
+pub struct PyArrayLike<'py, T, D>(&'py PyArray<T, D>);
+
+/// One-dimensional array-like objects.
+pub type PyArrayLike1<'py, T> = PyArrayLike<'py, T, ndarray::Ix1>;
+/// Two-dimensional array-like objects.
+pub type PyArrayLike2<'py, T> = PyArrayLike<'py, T, ndarray::Ix2>;
+/// Dynamic-dimensional array-like objects.
+pub type PyArrayLikeDyn<'py, T> = PyArrayLike<'py, T, ndarray::IxDyn>;
+
+impl<'py, T, D> PyArrayLike<'py, T, D> {
+    /// Returns the extracted array.
+    pub fn as_array_ref(&self) -> &'py PyArray<T, D> {
+        self.0
+    }
+}
+
+impl<'py, T, D> Deref for PyArrayLike<'py, T, D> {
+    type Target = PyArray<T, D>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'py, T: Element, D: Dimension> FromPyObject<'py> for PyArrayLike<'py, T, D> {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        PyArray::from_array_like(ob).map(Self)
+    }
+}
+
+impl<T: Element, D: Dimension> PyArray<T, D> {
+    /// Extracts an array from `obj` which is either a NumPy array or implements
+    /// `__array_interface__` or `__array__`.
+    ///
+    /// See [`PyArrayLike`] for a wrapper which can be used as a function argument.
+    ///
+    /// An error is returned if the data type, dimensionality or strides provided by
+    /// the protocol are not supported by `PyArray<T, D>`, e.g. because they are not aligned.
+    pub fn from_array_like<'py>(obj: &'py PyAny) -> PyResult<&'py Self> {
+        if unsafe { npyffi::PyArray_Check(obj.as_ptr()) } != 0 {
+            return obj.extract();
+        }
+
+        let py = obj.py();
+        let from_interface = unsafe { PY_ARRAY_API.PyArray_FromInterface(obj.as_ptr()) };
+        let array = unsafe { from_interface_result(py, from_interface) }.map_err(|err| {
+            PyTypeError::new_err(format!(
+                "unsupported __array_interface__ of {}: {}",
+                type_name(obj),
+                err
+            ))
+        })?;
+        let array = match array {
+            Some(array) => array,
+            None => {
+                let from_attr = unsafe {
+                    PY_ARRAY_API.PyArray_FromArrayAttr(
+                        obj.as_ptr(),
+                        std::ptr::null_mut(),
+                        std::ptr::null_mut(),
+                    )
+                };
+                unsafe { from_interface_result(py, from_attr) }?.ok_or_else(|| {
+                    PyTypeError::new_err(format!(
+                        "{} is neither an array nor implements __array_interface__ or __array__",
+                        type_name(obj)
+                    ))
+                })?
+            }
+        };
+        let array: &Self = array
+            .extract()
+            .map_err(|err| unsupported_array(obj, array, err))?;
+        array
+            .check_aligned()
+            .map_err(|err| unsupported_array(obj, array.as_ref(), err.into()))?;
+        Ok(array)
+    }
+}
+
+/// Converts the result of `PyArray_FromInterface` or `PyArray_FromArrayAttr`,
+/// which return a borrowed `NotImplemented` if the object does not provide the respective protocol.
+unsafe fn from_interface_result(
+    py: pyo3::Python,
+    ptr: *mut ffi::PyObject,
+) -> PyResult<Option<&PyAny>> {
+    if ptr.is_null() {
+        Err(PyErr::fetch(py))
+    } else if ptr == ffi::Py_NotImplemented() {
+        Ok(None)
+    } else {
+        Ok(Some(py.from_owned_ptr(ptr)))
+    }
+}
+
+/// Describes why the array created from the protocol of `obj` cannot be used.
+fn unsupported_array(obj: &PyAny, array: &PyAny, err: PyErr) -> PyErr {
+    let describe = |value: PyResult<&PyAny>| {
+        value
+            .and_then(PyAny::str)
+            .map(|value| value.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| "<unknown>".to_owned())
+    };
+    PyTypeError::new_err(format!(
+        "the array provided by {} with typestr {} and strides {} is not supported: {}",
+        type_name(obj),
+        describe(
+            array
+                .getattr("dtype")
+                .and_then(|dtype| dtype.getattr("str"))
+        ),
+        describe(array.getattr("strides")),
+        err
+    ))
+}
+
+fn type_name(obj: &PyAny) -> &str {
+    obj.get_type().name().unwrap_or("<unknown>")
+}
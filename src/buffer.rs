@@ -0,0 +1,192 @@
+//! Support for the Python buffer protocol as specified by PEP 3118
+use std::{ffi::CStr, mem, os::raw::c_long};
+
+use ndarray::{Dimension, IxDyn};
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::{PyBufferError, PyValueError},
+    ffi, AsPyPointer, PyAny, PyErr, PyResult, Python,
+};
+
+use crate::dtype::{Element, PyArrayDescr};
+use crate::error::{DimensionalityError, NotAlignedError, TypeError};
+use crate::npyffi::{self, npy_intp};
+use crate::slice_container::SliceOwner;
+use crate::PyArray;
+
+/// Keeps a buffer exported by another object alive while it backs an array.
+struct BufferOwner(Box<ffi::Py_buffer>);
+
+// The buffer is only released in `drop` which acquires the GIL.
+unsafe impl Send for BufferOwner {}
+
+impl Drop for BufferOwner {
+    fn drop(&mut self) {
+        Python::with_gil(|_| unsafe { ffi::PyBuffer_Release(&mut *self.0) });
+    }
+}
+
+impl<T: Element, D: Dimension> PyArray<T, D> {
+    /// Creates an array viewing the memory of `obj` which must implement the buffer protocol,
+    /// e.g. `bytes`, `bytearray`, `memoryview` or `array.array`.
+    ///
+    /// The returned array shares its data with `obj` and keeps the buffer alive until it is dropped.
+    /// It is writeable if and only if the buffer is writeable.
+    ///
+    /// The item format of the buffer has to be equivalent to the element type `T`
+    /// and its dimensionality has to match `D`, otherwise an error is returned.
+    /// An error is also returned if the buffer is not aligned as required by `T`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray1;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let obj = py.eval("__import__('array').array('d', [1.0, 2.0, 3.0])", None, None).unwrap();
+    ///     let array = PyArray1::<f64>::from_buffer(obj).unwrap();
+    ///     assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[1.0, 2.0, 3.0]);
+    ///
+    ///     assert!(PyArray1::<f32>::from_buffer(obj).is_err());
+    /// });
+    /// ```
+    pub fn from_buffer<'py>(obj: &'py PyAny) -> PyResult<&'py Self> {
+        let py = obj.py();
+        if !T::IS_COPY {
+            return Err(PyValueError::new_err(
+                "cannot create an array of objects from a buffer",
+            ));
+        }
+
+        let mut buf = Box::new(mem::MaybeUninit::<ffi::Py_buffer>::uninit());
+        let buf = unsafe {
+            if ffi::PyObject_GetBuffer(obj.as_ptr(), buf.as_mut_ptr(), ffi::PyBUF_RECORDS_RO) == -1
+            {
+                return Err(PyErr::fetch(py));
+            }
+            // `PyObject_GetBuffer` initialized the buffer and it is released when `buf` is dropped.
+            BufferOwner(Box::from_raw(Box::into_raw(buf) as *mut ffi::Py_buffer))
+        };
+        let raw = &*buf.0;
+
+        let format = if raw.format.is_null() {
+            "B"
+        } else {
+            unsafe { CStr::from_ptr(raw.format) }
+                .to_str()
+                .map_err(|_| PyBufferError::new_err("buffer format is not valid UTF-8"))?
+        };
+        let src_dtype = descr_from_format(py, format)?;
+        let dst_dtype = T::get_dtype(py);
+        if !src_dtype.is_equiv_to(dst_dtype) || raw.itemsize as usize != dst_dtype.itemsize() {
+            return Err(TypeError::new(src_dtype, dst_dtype).into());
+        }
+
+        let ndim = raw.ndim as usize;
+        if let Some(expected) = D::NDIM {
+            if ndim != expected {
+                return Err(DimensionalityError::new(ndim, expected).into());
+            }
+        }
+        let (shape, strides) = if ndim == 0 {
+            (Vec::new(), Vec::new())
+        } else {
+            unsafe {
+                let shape = std::slice::from_raw_parts(raw.shape, ndim);
+                let strides = std::slice::from_raw_parts(raw.strides, ndim);
+                (
+                    shape.iter().map(|&dim| dim as usize).collect::<Vec<_>>(),
+                    strides.iter().map(|&stride| stride as npy_intp).collect(),
+                )
+            }
+        };
+        let dims = D::from_dimension(&IxDyn(&shape)).unwrap();
+
+        let align = mem::align_of::<T>();
+        let aligned = raw.buf as usize % align == 0
+            && shape
+                .iter()
+                .zip(&strides)
+                .all(|(&len, &stride)| len <= 1 || stride % align as npy_intp == 0);
+        if !aligned {
+            return Err(NotAlignedError::new(align).into());
+        }
+
+        let flag = if raw.readonly == 0 {
+            npyffi::NPY_ARRAY_WRITEABLE
+        } else {
+            0
+        };
+        let data_ptr = raw.buf as *const T;
+        Ok(unsafe {
+            PyArray::from_raw_parts_with_flag(
+                py,
+                dims,
+                strides.as_ptr(),
+                data_ptr,
+                flag,
+                SliceOwner(Box::new(buf)),
+            )
+        })
+    }
+
+    /// Returns a [`PyBuffer`] view of this array using the buffer protocol.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = PyArray::arange(py, 0, 4, 1).reshape([2, 2]).unwrap();
+    ///     let buffer = array.as_buffer().unwrap();
+    ///     assert_eq!(buffer.shape(), &[2, 2]);
+    ///     assert_eq!(buffer.to_vec(py).unwrap(), [0, 1, 2, 3]);
+    /// });
+    /// ```
+    pub fn as_buffer(&self) -> PyResult<PyBuffer<T>>
+    where
+        T: pyo3::buffer::Element,
+    {
+        PyBuffer::get(self.as_ref())
+    }
+}
+
+/// Translates a PEP 3118 item format into the equivalent NumPy descriptor.
+///
+/// Only formats describing a single primitive item are supported.
+fn descr_from_format<'py>(py: Python<'py>, format: &str) -> PyResult<&'py PyArrayDescr> {
+    let unsupported = || PyBufferError::new_err(format!("unsupported buffer format {:?}", format));
+
+    let (byteorder, native_size, code) = match format.as_bytes().first() {
+        Some(b'@') => ('=', true, &format[1..]),
+        Some(b'=') => ('=', false, &format[1..]),
+        Some(b'<') => ('<', false, &format[1..]),
+        Some(b'>') | Some(b'!') => ('>', false, &format[1..]),
+        _ => ('=', true, format),
+    };
+    let code = code.strip_prefix('1').unwrap_or(code);
+
+    let (kind, size) = match code {
+        "?" => ('b', 1),
+        "b" => ('i', 1),
+        "B" => ('u', 1),
+        "h" => ('i', 2),
+        "H" => ('u', 2),
+        "i" => ('i', 4),
+        "I" => ('u', 4),
+        "l" if native_size => ('i', mem::size_of::<c_long>()),
+        "L" if native_size => ('u', mem::size_of::<c_long>()),
+        "l" => ('i', 4),
+        "L" => ('u', 4),
+        "q" => ('i', 8),
+        "Q" => ('u', 8),
+        "n" if native_size => ('i', mem::size_of::<isize>()),
+        "N" if native_size => ('u', mem::size_of::<usize>()),
+        "e" => ('f', 2),
+        "f" => ('f', 4),
+        "d" => ('f', 8),
+        "Zf" => ('c', 8),
+        "Zd" => ('c', 16),
+        _ => return Err(unsupported()),
+    };
+    let byteorder = if size == 1 { '|' } else { byteorder };
+
+    PyArrayDescr::new(py, &format!("{}{}{}", byteorder, kind, size))
+}
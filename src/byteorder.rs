@@ -0,0 +1,192 @@
+//! Support for arrays whose byte order differs from the native one of the current platform
+use std::fmt;
+
+use ndarray::Dimension;
+use num_complex::Complex;
+use pyo3::{AsPyPointer, PyAny, PyErr, PyResult, Python};
+
+use crate::dtype::{ByteOrder, Element, PyArrayDescr};
+use crate::npyffi::{self, PY_ARRAY_API};
+use crate::PyArray;
+
+/// Primitive types which can be stored in the opposite byte order using [`NonNative`].
+pub trait ByteSwap: Element + Copy {
+    /// Reverses the byte order of `self`.
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_byte_swap_int {
+    ($($ty: ty),+) => {
+        $(
+            impl ByteSwap for $ty {
+                fn swap_bytes(self) -> Self {
+                    <$ty>::swap_bytes(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_byte_swap_int!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+macro_rules! impl_byte_swap_float {
+    ($($ty: ty),+) => {
+        $(
+            impl ByteSwap for $ty {
+                fn swap_bytes(self) -> Self {
+                    <$ty>::from_bits(self.to_bits().swap_bytes())
+                }
+            }
+
+            impl ByteSwap for Complex<$ty> {
+                fn swap_bytes(self) -> Self {
+                    Complex::new(self.re.swap_bytes(), self.im.swap_bytes())
+                }
+            }
+        )+
+    };
+}
+
+impl_byte_swap_float!(f32, f64);
+
+/// An element of type `T` stored in the byte order opposite to the native one,
+/// e.g. big endian on x86-64, which is decoded on access.
+///
+/// Its data type is the one of `T` with swapped byte order, so that arrays of non-native data
+/// can be extracted as `PyArray<NonNative<T>, D>` and used without copying.
+///
+/// # Example
+/// ```
+/// use numpy::{NonNative, PyArray1};
+/// use pyo3::types::IntoPyDict;
+/// pyo3::Python::with_gil(|py| {
+///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+///     let dtype = if cfg!(target_endian = "little") { ">f8" } else { "<f8" };
+///     let array: &PyArray1<NonNative<f64>> = py
+///         .eval(&format!("np.array([1.5, 2.5], dtype='{}')", dtype), Some(locals), None)
+///         .unwrap()
+///         .extract()
+///         .unwrap();
+///     let values = array.readonly().unwrap().as_array().map(|x| x.get());
+///     assert_eq!(values, ndarray::array![1.5, 2.5]);
+///
+///     let native = py.eval("np.array([1.5, 2.5])", Some(locals), None).unwrap();
+///     assert!(native.extract::<&PyArray1<NonNative<f64>>>().is_err());
+/// });
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct NonNative<T>(T);
+
+impl<T: ByteSwap> NonNative<T> {
+    /// Encodes `value` in the non-native byte order.
+    pub fn new(value: T) -> Self {
+        Self(value.swap_bytes())
+    }
+
+    /// Decodes the value stored in the non-native byte order.
+    pub fn get(self) -> T {
+        self.0.swap_bytes()
+    }
+
+    /// Replaces the stored value by `value` encoded in the non-native byte order.
+    pub fn set(&mut self, value: T) {
+        self.0 = value.swap_bytes();
+    }
+}
+
+impl<T: ByteSwap> From<T> for NonNative<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: ByteSwap + fmt::Debug> fmt::Debug for NonNative<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NonNative").field(&self.get()).finish()
+    }
+}
+
+unsafe impl<T: ByteSwap> Element for NonNative<T> {
+    const IS_COPY: bool = true;
+
+    fn get_dtype(py: Python) -> &PyArrayDescr {
+        // Swapping the byte order of built-in descriptors only fails if memory is exhausted.
+        T::get_dtype(py)
+            .with_byteorder(ByteOrder::Swap)
+            .expect("failed to swap the byte order of a data type")
+    }
+}
+
+impl<T: Element, D: Dimension> PyArray<T, D> {
+    /// Extracts an array from `ob` like its `FromPyObject` implementation but additionally
+    /// accepts arrays whose byte order is opposite to the native one, which are copied
+    /// into a new array using the native byte order.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray1;
+    /// use pyo3::types::IntoPyDict;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+    ///     let obj = py.eval("np.array([1, 2, 3], dtype='>i4')", Some(locals), None).unwrap();
+    ///     let array = PyArray1::<i32>::from_any_byteorder(obj).unwrap();
+    ///     assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3]);
+    /// });
+    /// ```
+    pub fn from_any_byteorder<'py>(ob: &'py PyAny) -> PyResult<&'py Self> {
+        let py = ob.py();
+        if unsafe { npyffi::PyArray_Check(ob.as_ptr()) } == 0 {
+            return ob.extract();
+        }
+        let array_ptr = ob.as_ptr() as *mut npyffi::PyArrayObject;
+        let src_dtype: &PyArrayDescr = unsafe { py.from_borrowed_ptr((*array_ptr).descr as _) };
+        let dst_dtype = T::get_dtype(py);
+        if src_dtype.is_native_byteorder() != Some(false)
+            || !src_dtype
+                .with_byteorder(ByteOrder::Native)?
+                .is_equiv_to(dst_dtype)
+        {
+            return ob.extract();
+        }
+
+        let copy = unsafe {
+            PY_ARRAY_API.PyArray_FromArray(
+                array_ptr,
+                dst_dtype.into_dtype_ptr(),
+                npyffi::NPY_ARRAY_ENSURECOPY,
+            )
+        };
+        if copy.is_null() {
+            return Err(PyErr::fetch(py));
+        }
+        unsafe { py.from_owned_ptr::<PyAny>(copy) }.extract()
+    }
+
+    /// Swaps the bytes of all elements like
+    /// [`np.ndarray.byteswap`](https://numpy.org/doc/stable/reference/generated/numpy.ndarray.byteswap.html),
+    /// either in place returning `self` or in a copy.
+    ///
+    /// Note that the data type is unchanged, so this is mostly useful to convert between
+    /// `PyArray<T, D>` and `PyArray<NonNative<T>, D>` data.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = PyArray::from_slice(py, &[0x0102_u16, 0x0304]);
+    ///     let swapped = array.byteswap(false).unwrap();
+    ///     assert_eq!(swapped.readonly().unwrap().as_slice().unwrap(), &[0x0201, 0x0403]);
+    ///     assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[0x0102, 0x0304]);
+    /// });
+    /// ```
+    pub fn byteswap(&self, inplace: bool) -> PyResult<&Self> {
+        let py = self.py();
+        let ptr = unsafe { PY_ARRAY_API.PyArray_Byteswap(self.as_array_ptr(), inplace as _) };
+        if ptr.is_null() {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(unsafe { PyArray::from_owned_ptr(py, ptr) })
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use ndarray::{ArrayBase, Data, Dimension, IntoDimension, Ix1, OwnedRepr};
 use pyo3::Python;
 
-use std::{mem, os::raw::c_int};
+use std::{mem, os::raw::c_int, sync::Arc};
 
 use crate::{
     npyffi::{self, npy_intp},
@@ -23,7 +23,7 @@ use crate::{
 /// use numpy::{PyArray, IntoPyArray};
 /// pyo3::Python::with_gil(|py| {
 ///     let py_array = vec![1, 2, 3].into_pyarray(py);
-///     assert_eq!(py_array.readonly().as_slice().unwrap(), &[1, 2, 3]);
+///     assert_eq!(py_array.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3]);
 ///     assert!(py_array.resize(100).is_err()); // You can't resize owned-by-rust array.
 /// });
 /// ```
@@ -55,6 +55,34 @@ impl<T: Element> IntoPyArray for Vec<T> {
     }
 }
 
+/// The resulting array shares the data with other clones of the `Arc` and is therefore readonly.
+impl<T: Element + Sync + 'static> IntoPyArray for Arc<[T]> {
+    type Item = T;
+    type Dim = Ix1;
+    fn into_pyarray<'py>(self, py: Python<'py>) -> &'py PyArray<Self::Item, Self::Dim> {
+        PyArray::from_owner(py, self)
+    }
+}
+
+/// The resulting array shares the data with other clones of the `Arc` and is therefore readonly.
+impl<T: Element + Sync + 'static> IntoPyArray for Arc<Vec<T>> {
+    type Item = T;
+    type Dim = Ix1;
+    fn into_pyarray<'py>(self, py: Python<'py>) -> &'py PyArray<Self::Item, Self::Dim> {
+        PyArray::from_owner(py, self)
+    }
+}
+
+/// The resulting array shares the data with other clones of the `Bytes` and is therefore readonly.
+#[cfg(feature = "bytes")]
+impl IntoPyArray for bytes::Bytes {
+    type Item = u8;
+    type Dim = Ix1;
+    fn into_pyarray<'py>(self, py: Python<'py>) -> &'py PyArray<Self::Item, Self::Dim> {
+        PyArray::from_owner(py, self)
+    }
+}
+
 impl<A, D> IntoPyArray for ArrayBase<OwnedRepr<A>, D>
 where
     A: Element,
@@ -76,7 +104,7 @@ where
 /// use numpy::{PyArray, ToPyArray};
 /// pyo3::Python::with_gil(|py| {
 ///     let py_array = vec![1, 2, 3].to_pyarray(py);
-///     assert_eq!(py_array.readonly().as_slice().unwrap(), &[1, 2, 3]);
+///     assert_eq!(py_array.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3]);
 /// });
 /// ```
 ///
@@ -92,7 +120,7 @@ where
 ///     let sliced = arr3(&[[[ 1,  2,  3]],
 ///                         [[ 7,  8,  9]]]);
 ///     let py_slice = slice.to_pyarray(py);
-///     assert_eq!(py_slice.readonly().as_array(), sliced);
+///     assert_eq!(py_slice.readonly().unwrap().as_array(), sliced);
 ///     pyo3::py_run!(py, py_slice, "assert py_slice.flags['C_CONTIGUOUS']");
 /// });
 /// ```
@@ -203,7 +231,7 @@ impl NpyStrides {
             NpyStrides::Long(inner) => inner.as_ptr(),
         }
     }
-    fn from_dim<D: Dimension>(dim: &D, type_size: usize) -> Self {
+    pub(crate) fn from_dim<D: Dimension>(dim: &D, type_size: usize) -> Self {
         Self::new(
             dim.default_strides()
                 .slice()
@@ -0,0 +1,462 @@
+//! Exchanging arrays with other libraries using the [DLPack][dlpack] protocol
+//!
+//! This module contains the C types of the DLPack ABI needed to implement
+//! [`PyArray::from_dlpack`] and [`PyArray::to_dlpack`]. Only tensors residing in CPU memory are supported.
+//!
+//! [dlpack]: https://dmlc.github.io/dlpack/latest/
+use std::os::raw::{c_char, c_void};
+use std::{mem, ptr, slice};
+
+use ndarray::{Dimension, IxDyn};
+use pyo3::{
+    exceptions::{PyBufferError, PyTypeError, PyValueError},
+    ffi,
+    types::IntoPyDict,
+    AsPyPointer, PyAny, PyErr, PyObject, PyResult, Python, ToPyObject,
+};
+
+use crate::dtype::{Element, PyArrayDescr};
+use crate::error::{DimensionalityError, NotAlignedError, TypeError};
+use crate::npyffi::{self, npy_intp};
+use crate::slice_container::SliceOwner;
+use crate::PyArray;
+
+const DLTENSOR: &[u8] = b"dltensor\0";
+const USED_DLTENSOR: &[u8] = b"used_dltensor\0";
+const DLTENSOR_VERSIONED: &[u8] = b"dltensor_versioned\0";
+const USED_DLTENSOR_VERSIONED: &[u8] = b"used_dltensor_versioned\0";
+
+/// The flag of a [`DLManagedTensorVersioned`] indicating that its data must not be written.
+pub const DLPACK_FLAG_BITMASK_READ_ONLY: u64 = 1 << 0;
+
+/// The device type of tensors residing in CPU memory.
+pub const KDLCPU: i32 = 1;
+
+/// The device a tensor resides on.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DLDevice {
+    pub device_type: i32,
+    pub device_id: i32,
+}
+
+/// The element type of a tensor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DLDataType {
+    /// One of the `KDL*` type codes.
+    pub code: u8,
+    /// The number of bits of each lane.
+    pub bits: u8,
+    /// The number of lanes, larger than one only for vector types.
+    pub lanes: u16,
+}
+
+impl DLDataType {
+    pub const KDLINT: u8 = 0;
+    pub const KDLUINT: u8 = 1;
+    pub const KDLFLOAT: u8 = 2;
+    pub const KDLCOMPLEX: u8 = 5;
+    pub const KDLBOOL: u8 = 6;
+
+    /// Returns the DLPack type corresponding to the element type `T`.
+    ///
+    /// This fails for object types which cannot be exchanged via DLPack.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::dlpack::DLDataType;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let dtype = DLDataType::of::<f32>(py).unwrap();
+    ///     assert_eq!(dtype, DLDataType { code: DLDataType::KDLFLOAT, bits: 32, lanes: 1 });
+    /// });
+    /// ```
+    pub fn of<T: Element>(py: Python) -> PyResult<Self> {
+        Self::from_descr(T::get_dtype(py))
+    }
+
+    fn from_descr(descr: &PyArrayDescr) -> PyResult<Self> {
+        let code = match descr.kind() {
+            b'b' => Self::KDLBOOL,
+            b'i' => Self::KDLINT,
+            b'u' => Self::KDLUINT,
+            b'f' => Self::KDLFLOAT,
+            b'c' => Self::KDLCOMPLEX,
+            _ => {
+                return Err(PyTypeError::new_err(format!(
+                    "data type {} is not supported by DLPack",
+                    descr
+                )))
+            }
+        };
+        let bits = descr.itemsize() * 8;
+        if bits > u8::MAX as usize {
+            return Err(PyTypeError::new_err(format!(
+                "data type {} is too large to be supported by DLPack",
+                descr
+            )));
+        }
+        Ok(Self {
+            code,
+            bits: bits as u8,
+            lanes: 1,
+        })
+    }
+
+    fn to_descr<'py>(self, py: Python<'py>) -> PyResult<&'py PyArrayDescr> {
+        let kind = match self.code {
+            Self::KDLBOOL => 'b',
+            Self::KDLINT => 'i',
+            Self::KDLUINT => 'u',
+            Self::KDLFLOAT => 'f',
+            Self::KDLCOMPLEX => 'c',
+            _ => return Err(self.unsupported()),
+        };
+        if self.lanes != 1 || self.bits % 8 != 0 {
+            return Err(self.unsupported());
+        }
+        PyArrayDescr::new(py, &format!("={}{}", kind, self.bits / 8))
+    }
+
+    fn unsupported(self) -> PyErr {
+        PyTypeError::new_err(format!("DLPack data type {:?} is not supported", self))
+    }
+}
+
+/// A strided view of memory, the main structure exchanged via DLPack.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLTensor {
+    pub data: *mut c_void,
+    pub device: DLDevice,
+    pub ndim: i32,
+    pub dtype: DLDataType,
+    pub shape: *mut i64,
+    /// Strides in number of elements, a null pointer indicates a C-contiguous tensor.
+    pub strides: *mut i64,
+    pub byte_offset: u64,
+}
+
+/// A [`DLTensor`] together with the means to release it once the consumer is done with it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLManagedTensor {
+    pub dl_tensor: DLTensor,
+    pub manager_ctx: *mut c_void,
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+/// The version of the DLPack ABI.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DLPackVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+/// A [`DLManagedTensor`] which additionally carries the ABI version and flags like
+/// [`DLPACK_FLAG_BITMASK_READ_ONLY`], exchanged by producers supporting DLPack 1.0.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLManagedTensorVersioned {
+    pub version: DLPackVersion,
+    pub manager_ctx: *mut c_void,
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensorVersioned)>,
+    pub flags: u64,
+    pub dl_tensor: DLTensor,
+}
+
+/// Releases an imported tensor once the array viewing it is dropped.
+enum ManagedTensorOwner {
+    Legacy(*mut DLManagedTensor),
+    Versioned(*mut DLManagedTensorVersioned),
+}
+
+// The producer is responsible for making the deleter callable from any thread.
+unsafe impl Send for ManagedTensorOwner {}
+
+impl ManagedTensorOwner {
+    fn dl_tensor(&self) -> &DLTensor {
+        unsafe {
+            match *self {
+                Self::Legacy(tensor) => &(*tensor).dl_tensor,
+                Self::Versioned(tensor) => &(*tensor).dl_tensor,
+            }
+        }
+    }
+
+    /// Only versioned tensors can signal that they may be written,
+    /// so legacy tensors are conservatively treated as readonly.
+    fn is_writeable(&self) -> bool {
+        match *self {
+            Self::Legacy(_) => false,
+            Self::Versioned(tensor) => unsafe {
+                (*tensor).flags & DLPACK_FLAG_BITMASK_READ_ONLY == 0
+            },
+        }
+    }
+}
+
+impl Drop for ManagedTensorOwner {
+    fn drop(&mut self) {
+        unsafe {
+            match *self {
+                Self::Legacy(tensor) => {
+                    if let Some(deleter) = (*tensor).deleter {
+                        deleter(tensor);
+                    }
+                }
+                Self::Versioned(tensor) => {
+                    if let Some(deleter) = (*tensor).deleter {
+                        deleter(tensor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keeps an exported array and the shape and strides referenced by the tensor alive.
+struct ExportContext {
+    tensor: DLManagedTensor,
+    _shape: Vec<i64>,
+    _strides: Vec<i64>,
+    _array: PyObject,
+}
+
+unsafe extern "C" fn delete_exported(tensor: *mut DLManagedTensor) {
+    let ctx = Box::from_raw((*tensor).manager_ctx as *mut ExportContext);
+    // Consumers may release the tensor without holding the GIL.
+    Python::with_gil(|_| drop(ctx));
+}
+
+unsafe extern "C" fn destruct_capsule(capsule: *mut ffi::PyObject) {
+    // The tensor is owned by a consumer once the capsule was renamed.
+    if ffi::PyCapsule_IsValid(capsule, DLTENSOR.as_ptr() as *const c_char) == 1 {
+        let tensor = ffi::PyCapsule_GetPointer(capsule, DLTENSOR.as_ptr() as *const c_char);
+        delete_exported(tensor as *mut DLManagedTensor);
+    }
+}
+
+impl<T: Element, D: Dimension> PyArray<T, D> {
+    /// Imports the tensor exported by `obj` using its `__dlpack__` method without copying its data.
+    ///
+    /// Only tensors residing in CPU memory can be imported. The element type of the tensor
+    /// has to be equivalent to `T` and its dimensionality has to match `D`.
+    ///
+    /// The deleter of the tensor is called when the returned array is garbage collected.
+    ///
+    /// The returned array is writeable only if the producer supports DLPack 1.0 and does not mark
+    /// the tensor as readonly, as the legacy protocol cannot express whether writing is allowed.
+    /// An error is returned if the data of the tensor is not aligned as required by `T`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray1;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = PyArray1::from_slice(py, &[1_i64, 2, 3]);
+    ///     let imported = PyArray1::<i64>::from_dlpack(array).unwrap();
+    ///     assert_eq!(imported.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3]);
+    /// });
+    /// ```
+    pub fn from_dlpack<'py>(obj: &'py PyAny) -> PyResult<&'py Self> {
+        let py = obj.py();
+
+        if obj.hasattr("__dlpack_device__")? {
+            let (device_type, _): (i32, i32) = obj.call_method0("__dlpack_device__")?.extract()?;
+            if device_type != KDLCPU {
+                return Err(PyBufferError::new_err(
+                    "only DLPack tensors residing in CPU memory are supported",
+                ));
+            }
+        }
+
+        // Producers which do not support DLPack 1.0 reject the `max_version` keyword.
+        let kwargs = [("max_version", (1, 0))].into_py_dict(py);
+        let capsule = match obj.call_method("__dlpack__", (), Some(kwargs)) {
+            Ok(capsule) => capsule,
+            Err(err) if err.is_instance::<PyTypeError>(py) => obj.call_method0("__dlpack__")?,
+            Err(err) => return Err(err),
+        };
+        let tensor = unsafe {
+            let versioned = DLTENSOR_VERSIONED.as_ptr() as *const c_char;
+            let legacy = DLTENSOR.as_ptr() as *const c_char;
+            let (tensor, used) = if ffi::PyCapsule_IsValid(capsule.as_ptr(), versioned) == 1 {
+                let tensor = ffi::PyCapsule_GetPointer(capsule.as_ptr(), versioned)
+                    as *mut DLManagedTensorVersioned;
+                if (*tensor).version.major != 1 {
+                    return Err(PyValueError::new_err(format!(
+                        "DLPack version {}.{} is not supported",
+                        (*tensor).version.major,
+                        (*tensor).version.minor
+                    )));
+                }
+                (
+                    ManagedTensorOwner::Versioned(tensor),
+                    USED_DLTENSOR_VERSIONED,
+                )
+            } else if ffi::PyCapsule_IsValid(capsule.as_ptr(), legacy) == 1 {
+                let tensor =
+                    ffi::PyCapsule_GetPointer(capsule.as_ptr(), legacy) as *mut DLManagedTensor;
+                (ManagedTensorOwner::Legacy(tensor), USED_DLTENSOR)
+            } else {
+                return Err(PyValueError::new_err(
+                    "__dlpack__ did not return an unconsumed dltensor capsule",
+                ));
+            };
+            // Mark the capsule as consumed so that its destructor does not release the tensor.
+            if ffi::PyCapsule_SetName(capsule.as_ptr(), used.as_ptr() as *const c_char) != 0 {
+                mem::forget(tensor);
+                return Err(PyErr::fetch(py));
+            }
+            tensor
+        };
+        let dl_tensor = tensor.dl_tensor();
+
+        if dl_tensor.device.device_type != KDLCPU {
+            return Err(PyBufferError::new_err(
+                "only DLPack tensors residing in CPU memory are supported",
+            ));
+        }
+
+        let src_dtype = dl_tensor.dtype.to_descr(py)?;
+        let dst_dtype = T::get_dtype(py);
+        if !src_dtype.is_equiv_to(dst_dtype) {
+            return Err(TypeError::new(src_dtype, dst_dtype).into());
+        }
+
+        let ndim = dl_tensor.ndim as usize;
+        if let Some(expected) = D::NDIM {
+            if ndim != expected {
+                return Err(DimensionalityError::new(ndim, expected).into());
+            }
+        }
+
+        let shape = if ndim == 0 {
+            Vec::new()
+        } else {
+            unsafe { slice::from_raw_parts(dl_tensor.shape, ndim) }
+                .iter()
+                .map(|&dim| dim as usize)
+                .collect::<Vec<_>>()
+        };
+        let itemsize = mem::size_of::<T>() as npy_intp;
+        let strides = if dl_tensor.strides.is_null() || ndim == 0 {
+            let mut strides = vec![itemsize; ndim];
+            for axis in (1..ndim).rev() {
+                strides[axis - 1] = strides[axis] * shape[axis] as npy_intp;
+            }
+            strides
+        } else {
+            unsafe { slice::from_raw_parts(dl_tensor.strides, ndim) }
+                .iter()
+                .map(|&stride| stride as npy_intp * itemsize)
+                .collect()
+        };
+        let dims = D::from_dimension(&IxDyn(&shape)).unwrap();
+        let data_ptr = unsafe {
+            (dl_tensor.data as *const u8).add(dl_tensor.byte_offset as usize) as *const T
+        };
+        // The strides are multiples of the item size, so only the data pointer needs checking.
+        if data_ptr as usize % mem::align_of::<T>() != 0 {
+            return Err(NotAlignedError::new(mem::align_of::<T>()).into());
+        }
+
+        let flag = if tensor.is_writeable() {
+            npyffi::NPY_ARRAY_WRITEABLE
+        } else {
+            0
+        };
+        Ok(unsafe {
+            PyArray::from_raw_parts_with_flag(
+                py,
+                dims,
+                strides.as_ptr(),
+                data_ptr,
+                flag,
+                SliceOwner(Box::new(tensor)),
+            )
+        })
+    }
+
+    /// Exports this array as a DLPack capsule named `dltensor` without copying its data.
+    ///
+    /// The capsule keeps the array alive until the consumer calls the deleter of the tensor.
+    /// Like NumPy, this fails for readonly arrays, object arrays
+    /// and arrays whose strides are not a multiple of their item size.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray1;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = PyArray1::from_slice(py, &[1.0_f32, 2.0]);
+    ///     let capsule = array.to_dlpack().unwrap();
+    ///     pyo3::py_run!(py, capsule, "assert type(capsule).__name__ == 'PyCapsule'");
+    /// });
+    /// ```
+    pub fn to_dlpack(&self) -> PyResult<PyObject> {
+        let py = self.py();
+        let dtype = DLDataType::from_descr(self.dtype())?;
+
+        if self.get_flag() & npyffi::NPY_ARRAY_WRITEABLE == 0 {
+            return Err(PyBufferError::new_err(
+                "cannot export readonly arrays via DLPack",
+            ));
+        }
+
+        let itemsize = mem::size_of::<T>() as isize;
+        if self.strides().iter().any(|stride| stride % itemsize != 0) {
+            return Err(PyBufferError::new_err(
+                "cannot export arrays with strides which are not a multiple of the item size",
+            ));
+        }
+
+        let mut shape = self
+            .shape()
+            .iter()
+            .map(|&dim| dim as i64)
+            .collect::<Vec<_>>();
+        let mut strides = self
+            .strides()
+            .iter()
+            .map(|&stride| (stride / itemsize) as i64)
+            .collect::<Vec<_>>();
+
+        let ctx = Box::into_raw(Box::new(ExportContext {
+            tensor: DLManagedTensor {
+                dl_tensor: DLTensor {
+                    data: unsafe { self.data() } as *mut c_void,
+                    device: DLDevice {
+                        device_type: KDLCPU,
+                        device_id: 0,
+                    },
+                    ndim: self.ndim() as i32,
+                    dtype,
+                    shape: shape.as_mut_ptr(),
+                    strides: strides.as_mut_ptr(),
+                    byte_offset: 0,
+                },
+                manager_ctx: ptr::null_mut(),
+                deleter: Some(delete_exported),
+            },
+            _shape: shape,
+            _strides: strides,
+            _array: self.to_object(py),
+        }));
+
+        unsafe {
+            (*ctx).tensor.manager_ctx = ctx as *mut c_void;
+            let tensor = &mut (*ctx).tensor as *mut DLManagedTensor;
+            let capsule = ffi::PyCapsule_New(
+                tensor as *mut c_void,
+                DLTENSOR.as_ptr() as *const c_char,
+                Some(destruct_capsule),
+            );
+            if capsule.is_null() {
+                delete_exported(tensor);
+                return Err(PyErr::fetch(py));
+            }
+            Ok(PyObject::from_owned_ptr(py, capsule))
+        }
+    }
+}
@@ -14,8 +14,8 @@ use pyo3::{
 };
 
 use crate::npyffi::{
-    NpyTypes, PyArray_Descr, NPY_ALIGNED_STRUCT, NPY_BYTEORDER_CHAR, NPY_ITEM_HASOBJECT, NPY_TYPES,
-    PY_ARRAY_API,
+    NpyTypes, PyArray_Descr, NPY_ALIGNED_STRUCT, NPY_BYTEORDER_CHAR, NPY_CASTING,
+    NPY_ITEM_HASOBJECT, NPY_TYPES, PY_ARRAY_API,
 };
 
 pub use num_complex::{Complex32, Complex64};
@@ -75,6 +75,144 @@ impl PyArrayDescr {
         }
     }
 
+    fn new_aligned_impl<'py>(py: Python<'py>, obj: PyObject) -> PyResult<&'py Self> {
+        let mut descr: *mut PyArray_Descr = ptr::null_mut();
+        unsafe {
+            PY_ARRAY_API.PyArray_DescrAlignConverter2(obj.as_ptr(), &mut descr as *mut _);
+            py.from_owned_ptr_or_err(descr as _)
+        }
+    }
+
+    /// Creates a structured data type from its fields given as `(name, dtype, offset)`
+    /// and its total size in bytes.
+    ///
+    /// If `aligned` is true, the offsets are checked to respect the alignment of the fields
+    /// like a C struct would, c.f. [`is_aligned_struct`](#method.is_aligned_struct).
+    ///
+    /// Equivalent to `np.dtype({'names': ..., 'formats': ..., 'offsets': ..., 'itemsize': ...}, align=aligned)`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::{dtype, PyArrayDescr};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let descr = PyArrayDescr::structured(
+    ///         py,
+    ///         &[("id", dtype::<u32>(py), 0), ("value", dtype::<f64>(py), 8)],
+    ///         16,
+    ///         true,
+    ///     )
+    ///     .unwrap();
+    ///     assert_eq!(descr.names(), Some(vec!["id", "value"]));
+    ///     assert_eq!(descr.get_field("value").unwrap().1, 8);
+    ///     assert_eq!(descr.itemsize(), 16);
+    ///     assert!(descr.is_aligned_struct());
+    /// });
+    /// ```
+    pub fn structured<'py>(
+        py: Python<'py>,
+        fields: &[(&str, &PyArrayDescr, usize)],
+        itemsize: usize,
+        aligned: bool,
+    ) -> PyResult<&'py Self> {
+        let spec = PyDict::new(py);
+        spec.set_item(
+            "names",
+            fields.iter().map(|(name, _, _)| *name).collect::<Vec<_>>(),
+        )?;
+        spec.set_item(
+            "formats",
+            fields
+                .iter()
+                .map(|(_, descr, _)| descr.to_object(py))
+                .collect::<Vec<_>>(),
+        )?;
+        spec.set_item(
+            "offsets",
+            fields
+                .iter()
+                .map(|(_, _, offset)| *offset)
+                .collect::<Vec<_>>(),
+        )?;
+        spec.set_item("itemsize", itemsize)?;
+        if aligned {
+            Self::new_aligned_impl(py, spec.to_object(py))
+        } else {
+            Self::new_impl(py, spec.to_object(py))
+        }
+    }
+
+    /// Creates a data type describing sub-arrays of the given `shape` with elements of type `base`.
+    ///
+    /// Equivalent to `np.dtype((base, shape))`.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::{dtype, PyArrayDescr};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let descr = PyArrayDescr::subarray(dtype::<f32>(py), &[2, 3]).unwrap();
+    ///     assert_eq!(descr.shape(), [2, 3]);
+    ///     assert_eq!(descr.itemsize(), 24);
+    ///     assert!(descr.base().is_equiv_to(dtype::<f32>(py)));
+    /// });
+    /// ```
+    pub fn subarray<'py>(base: &'py PyArrayDescr, shape: &[usize]) -> PyResult<&'py Self> {
+        let py = base.py();
+        let shape = PyTuple::new(py, shape);
+        Self::new(py, &(base, shape))
+    }
+
+    /// Creates a data type of datetimes with the given `unit`.
+    ///
+    /// Equivalent to `np.dtype('datetime64[unit]')`.
+    pub fn datetime(py: Python, unit: DatetimeUnit) -> PyResult<&Self> {
+        Self::new(py, &format!("M8[{}]", unit.code()))
+    }
+
+    /// Creates a data type of time differences with the given `unit`.
+    ///
+    /// Equivalent to `np.dtype('timedelta64[unit]')`.
+    pub fn timedelta(py: Python, unit: DatetimeUnit) -> PyResult<&Self> {
+        Self::new(py, &format!("m8[{}]", unit.code()))
+    }
+
+    /// Creates a data type of byte strings with a length of `len` bytes.
+    ///
+    /// Equivalent to `np.dtype('S{len}')`.
+    pub fn string(py: Python, len: usize) -> PyResult<&Self> {
+        Self::new(py, &format!("S{}", len))
+    }
+
+    /// Creates a data type of Unicode strings with a length of `len` code points.
+    ///
+    /// Equivalent to `np.dtype('U{len}')`.
+    pub fn unicode(py: Python, len: usize) -> PyResult<&Self> {
+        Self::new(py, &format!("U{}", len))
+    }
+
+    /// Returns a copy of this data type with the given byte order.
+    ///
+    /// The byte order of the fields and the base type of structured and sub-array types
+    /// is changed as well. Data types for which byte order is not applicable are unchanged.
+    ///
+    /// Equivalent to [`np.dtype.newbyteorder`](https://numpy.org/doc/stable/reference/generated/numpy.dtype.newbyteorder.html).
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::{dtype, ByteOrder};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let big_endian = dtype::<i32>(py).with_byteorder(ByteOrder::Big).unwrap();
+    ///     assert_eq!(big_endian.byteorder(), b'>');
+    ///     assert_eq!(big_endian.is_native_byteorder(), Some(cfg!(target_endian = "big")));
+    /// });
+    /// ```
+    pub fn with_byteorder(&self, byteorder: ByteOrder) -> PyResult<&Self> {
+        unsafe {
+            let descr =
+                PY_ARRAY_API.PyArray_DescrNewByteorder(self.as_dtype_ptr(), byteorder.to_c_char());
+            self.py().from_owned_ptr_or_err(descr as _)
+        }
+    }
+
     /// Returns `self` as `*mut PyArray_Descr`.
     pub fn as_dtype_ptr(&self) -> *mut PyArray_Descr {
         self.as_ptr() as _
@@ -105,7 +243,8 @@ impl PyArrayDescr {
     fn from_npy_type(py: Python, npy_type: NPY_TYPES) -> &Self {
         unsafe {
             let descr = PY_ARRAY_API.PyArray_DescrFromType(npy_type as _);
-            py.from_owned_ptr(descr as _)
+            py.from_owned_ptr_or_err(descr as _)
+                .expect("NumPy failed to provide the descriptor of a built-in type")
         }
     }
 
@@ -306,6 +445,122 @@ impl PyArrayDescr {
         let offset = FromPyObject::extract(tuple.as_ref().get_item(1).unwrap()).unwrap();
         Ok((dtype, offset))
     }
+
+    /// Returns true if values of this type can be cast to `other` according to `casting`.
+    pub fn can_cast_to(&self, other: &Self, casting: Casting) -> bool {
+        unsafe {
+            PY_ARRAY_API.PyArray_CanCastTypeTo(
+                self.as_dtype_ptr(),
+                other.as_dtype_ptr(),
+                casting.to_c_enum(),
+            ) != 0
+        }
+    }
+}
+
+/// Controls which kinds of casts between data types are allowed,
+/// like the `casting` argument of many NumPy functions.
+///
+/// See [the numpy document](https://numpy.org/doc/stable/reference/generated/numpy.can_cast.html)
+/// for the meaning of each variant.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Casting {
+    /// Only identical types are allowed.
+    No,
+    /// Only byte order changes are allowed.
+    Equiv,
+    /// Only casts which preserve values are allowed.
+    Safe,
+    /// Safe casts and casts within a kind, e.g. from `f64` to `f32`, are allowed.
+    SameKind,
+    /// Any casts are allowed.
+    Unsafe,
+}
+
+impl Casting {
+    pub(crate) fn to_c_enum(self) -> NPY_CASTING {
+        match self {
+            Casting::No => NPY_CASTING::NPY_NO_CASTING,
+            Casting::Equiv => NPY_CASTING::NPY_EQUIV_CASTING,
+            Casting::Safe => NPY_CASTING::NPY_SAFE_CASTING,
+            Casting::SameKind => NPY_CASTING::NPY_SAME_KIND_CASTING,
+            Casting::Unsafe => NPY_CASTING::NPY_UNSAFE_CASTING,
+        }
+    }
+}
+
+/// The byte order of a data type, c.f. [`PyArrayDescr::with_byteorder`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ByteOrder {
+    /// Little endian, i.e. `<`.
+    Little,
+    /// Big endian, i.e. `>`.
+    Big,
+    /// The byte order of the current platform, i.e. `=`.
+    Native,
+    /// The opposite of the current byte order.
+    Swap,
+    /// Keeps the current byte order which is useful for data types
+    /// for which byte order is not applicable, i.e. `|`.
+    Ignore,
+}
+
+impl ByteOrder {
+    fn to_c_char(self) -> c_char {
+        let byteorder = match self {
+            ByteOrder::Little => NPY_BYTEORDER_CHAR::NPY_LITTLE,
+            ByteOrder::Big => NPY_BYTEORDER_CHAR::NPY_BIG,
+            ByteOrder::Native => NPY_BYTEORDER_CHAR::NPY_NATIVE,
+            ByteOrder::Swap => NPY_BYTEORDER_CHAR::NPY_SWAP,
+            ByteOrder::Ignore => NPY_BYTEORDER_CHAR::NPY_IGNORE,
+        };
+        byteorder as u8 as c_char
+    }
+}
+
+/// The units of datetime and timedelta data types, c.f. [`PyArrayDescr::datetime`].
+///
+/// See [the numpy document](https://numpy.org/doc/stable/reference/arrays.datetime.html#datetime-units)
+/// for their ranges.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DatetimeUnit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+    Picoseconds,
+    Femtoseconds,
+    Attoseconds,
+    /// A unit which is determined by the values assigned to it.
+    Generic,
+}
+
+impl DatetimeUnit {
+    fn code(self) -> &'static str {
+        use DatetimeUnit::*;
+        match self {
+            Years => "Y",
+            Months => "M",
+            Weeks => "W",
+            Days => "D",
+            Hours => "h",
+            Minutes => "m",
+            Seconds => "s",
+            Milliseconds => "ms",
+            Microseconds => "us",
+            Nanoseconds => "ns",
+            Picoseconds => "ps",
+            Femtoseconds => "fs",
+            Attoseconds => "as",
+            Generic => "generic",
+        }
+    }
 }
 
 /// Represents that a type can be an element of `PyArray`.
@@ -334,6 +589,8 @@ impl PyArrayDescr {
 /// and only `Py<PyAny>` can be stored in a type safe manner.
 ///
 /// You can however create `ndarray::Array<Py<T>, D>` and turn that into a NumPy array safely and efficiently using [`from_owned_object_array`][crate::PyArray::from_owned_object_array].
+/// Conversely, [`downcast_elements`][crate::PyReadonlyArray::downcast_elements] checks the type of every element once
+/// and returns a view of `Py<T>` which can be borrowed without further downcasts.
 pub unsafe trait Element: Clone + Send {
     /// Flag that indicates whether this type is trivially copyable.
     ///
@@ -113,3 +113,61 @@ impl fmt::Display for NotContiguousError {
 }
 
 impl_pyerr!(NotContiguousError);
+
+/// Represents that the array is not aligned as required by its element type.
+#[derive(Debug)]
+pub struct NotAlignedError {
+    align: usize,
+}
+
+impl NotAlignedError {
+    pub(crate) fn new(align: usize) -> Self {
+        NotAlignedError { align }
+    }
+}
+
+impl fmt::Display for NotAlignedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The given array is not aligned to the {} bytes required by its element type, \
+             consider using `PyArray::as_unaligned` or `PyArray::to_owned_array_unaligned`",
+            self.align
+        )
+    }
+}
+
+impl_pyerr!(NotAlignedError);
+
+/// Represents that the array cannot be viewed as a slice
+/// because it is either not contiguous or not aligned.
+#[derive(Debug)]
+pub enum AsSliceError {
+    /// The array is not contiguous.
+    NotContiguous(NotContiguousError),
+    /// The array is not aligned as required by its element type.
+    NotAligned(NotAlignedError),
+}
+
+impl fmt::Display for AsSliceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsSliceError::NotContiguous(err) => err.fmt(f),
+            AsSliceError::NotAligned(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<NotContiguousError> for AsSliceError {
+    fn from(err: NotContiguousError) -> Self {
+        AsSliceError::NotContiguous(err)
+    }
+}
+
+impl From<NotAlignedError> for AsSliceError {
+    fn from(err: NotAlignedError) -> Self {
+        AsSliceError::NotAligned(err)
+    }
+}
+
+impl_pyerr!(AsSliceError);
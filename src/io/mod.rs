@@ -0,0 +1,647 @@
+//! Reading and writing arrays in the [NPY format][npy].
+//!
+//! Files written by [`write_npy`] can be loaded by `np.load` and files written by `np.save`
+//! can be read using [`read_npy`]. All versions of the format (1.0, 2.0 and 3.0) are supported
+//! for reading, but object arrays, which are stored as pickles, are not.
+//!
+//! With the `npz` feature enabled, the [`npz`] module additionally provides
+//! reading and writing of `.npz` archives.
+//!
+//! # Example
+//! ```
+//! use numpy::{io, PyArray2};
+//! pyo3::Python::with_gil(|py| {
+//!     let array = numpy::pyarray![py, [1.0, 2.0], [3.0, 4.0]];
+//!     let mut buf = Vec::new();
+//!     io::write_npy(&mut buf, array).unwrap();
+//!     let read: &PyArray2<f64> = io::read_npy(py, &buf[..]).unwrap();
+//!     assert_eq!(read.readonly().unwrap().as_array(), ndarray::array![[1.0, 2.0], [3.0, 4.0]]);
+//! });
+//! ```
+//!
+//! [npy]: https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html
+use std::io::{Read, Write};
+use std::{mem, slice};
+
+use ndarray::{Array, ArrayBase, Data, Dimension};
+use pyo3::{
+    exceptions::PyValueError,
+    types::{PyDict, PyList, PyTuple},
+    PyErr, PyNativeType, PyObject, PyResult, Python, ToPyObject,
+};
+
+use crate::convert::ToNpyDims;
+use crate::dtype::{Element, PyArrayDescr};
+use crate::error::{DimensionalityError, TypeError};
+use crate::npyffi::{NPY_CASTING, NPY_ORDER, PY_ARRAY_API};
+use crate::PyArray;
+
+#[cfg(feature = "npz")]
+pub mod npz;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+const ALIGNMENT: usize = 64;
+/// The largest version 1.0 header accepted when reading, matching the default of `numpy.load`.
+///
+/// Headers of later versions are only written if they do not fit into version 1.0
+/// and are therefore read without a limit, but never trusting their stated length.
+const MAX_HEADER_LEN: usize = 10_000;
+
+/// Reads an array in the NPY format from `reader`.
+///
+/// The element type stored in the file must be equivalent to `T` up to byte order,
+/// data in non-native byte order is converted when reading.
+pub fn read_npy<'py, T, D, R>(py: Python<'py>, mut reader: R) -> PyResult<&'py PyArray<T, D>>
+where
+    T: Element,
+    D: Dimension,
+    R: Read,
+{
+    check_element::<T>()?;
+    let header = Header::read(py, &mut reader)?;
+
+    let dst_dtype = T::get_dtype(py);
+    let src_dtype = header.descr;
+    let can_cast = unsafe {
+        PY_ARRAY_API.PyArray_CanCastTypeTo(
+            src_dtype.as_dtype_ptr(),
+            dst_dtype.as_dtype_ptr(),
+            NPY_CASTING::NPY_EQUIV_CASTING,
+        )
+    };
+    if can_cast == 0 {
+        return Err(TypeError::new(src_dtype, dst_dtype).into());
+    }
+    if let Some(ndim) = D::NDIM {
+        if header.shape.len() != ndim {
+            return Err(DimensionalityError::new(header.shape.len(), ndim).into());
+        }
+    }
+
+    let dims = D::from_dimension(&ndarray::IxDyn(&header.shape)).unwrap();
+    let array = unsafe {
+        let ptr = PY_ARRAY_API.PyArray_Empty(
+            dims.ndim_cint(),
+            dims.as_dims_ptr(),
+            src_dtype.into_dtype_ptr(),
+            header.fortran_order as _,
+        );
+        if ptr.is_null() {
+            return Err(PyErr::fetch(py));
+        }
+        PyArray::<T, D>::from_owned_ptr(py, ptr)
+    };
+
+    let nbytes = array.len() * src_dtype.itemsize();
+    let data = unsafe { slice::from_raw_parts_mut(array.data() as *mut u8, nbytes) };
+    reader.read_exact(data)?;
+
+    if src_dtype.is_equiv_to(dst_dtype) {
+        Ok(array)
+    } else {
+        array.cast(header.fortran_order)
+    }
+}
+
+/// Reads an array in the NPY format from `reader` into an [`ndarray::Array`].
+///
+/// See [`read_npy`] for details.
+pub fn read_npy_array<T, D, R>(py: Python, reader: R) -> PyResult<Array<T, D>>
+where
+    T: Element,
+    D: Dimension,
+    R: Read,
+{
+    read_npy::<T, D, R>(py, reader).map(|array| array.to_owned_array())
+}
+
+/// Writes `array` to `writer` in the NPY format.
+///
+/// Arrays which are neither C- nor Fortran-contiguous are copied into C order first.
+pub fn write_npy<T, D, W>(mut writer: W, array: &PyArray<T, D>) -> PyResult<()>
+where
+    T: Element,
+    D: Dimension,
+    W: Write,
+{
+    check_element::<T>()?;
+    let py = array.py();
+
+    let array = if array.is_contiguous() {
+        array
+    } else {
+        unsafe {
+            let ptr = PY_ARRAY_API.PyArray_NewCopy(array.as_array_ptr(), NPY_ORDER::NPY_CORDER);
+            if ptr.is_null() {
+                return Err(PyErr::fetch(py));
+            }
+            PyArray::<T, D>::from_owned_ptr(py, ptr)
+        }
+    };
+    let fortran_order = array.is_fortran_contiguous() && !array.is_c_contiguous();
+
+    write_header(&mut writer, array.dtype(), fortran_order, array.shape())?;
+
+    let nbytes = array.len() * mem::size_of::<T>();
+    let data = unsafe { slice::from_raw_parts(array.data() as *const u8, nbytes) };
+    writer.write_all(data)?;
+    Ok(())
+}
+
+/// Writes an [`ndarray::ArrayBase`] to `writer` in the NPY format.
+///
+/// See [`write_npy`] for details.
+pub fn write_npy_array<S, D, W>(py: Python, mut writer: W, array: &ArrayBase<S, D>) -> PyResult<()>
+where
+    S: Data,
+    S::Elem: Element,
+    D: Dimension,
+    W: Write,
+{
+    check_element::<S::Elem>()?;
+    let dtype = S::Elem::get_dtype(py);
+
+    let (fortran_order, data) = if let Some(data) = array.as_slice() {
+        (false, Some(data))
+    } else if let Some(data) = array.t().to_slice() {
+        (true, Some(data))
+    } else {
+        (false, None)
+    };
+
+    write_header(&mut writer, dtype, fortran_order, array.shape())?;
+
+    match data {
+        Some(data) => writer.write_all(as_bytes(data))?,
+        None => {
+            for elem in array.iter() {
+                writer.write_all(as_bytes(slice::from_ref(elem)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_element<T: Element>() -> PyResult<()> {
+    if T::IS_COPY {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(
+            "object arrays are not supported by the NPY format implementation",
+        ))
+    }
+}
+
+fn as_bytes<T>(data: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, mem::size_of_val(data)) }
+}
+
+fn format_err(msg: impl Into<String>) -> PyErr {
+    PyValueError::new_err(format!("invalid NPY file: {}", msg.into()))
+}
+
+/// The contents of the header of an NPY file.
+struct Header<'py> {
+    descr: &'py PyArrayDescr,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+impl<'py> Header<'py> {
+    fn read<R: Read>(py: Python<'py>, reader: &mut R) -> PyResult<Self> {
+        let mut preamble = [0; 8];
+        reader.read_exact(&mut preamble)?;
+        if &preamble[..6] != MAGIC {
+            return Err(format_err("missing magic string"));
+        }
+        let (major, minor) = (preamble[6], preamble[7]);
+
+        let header_len = match major {
+            1 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            _ => {
+                return Err(format_err(format!(
+                    "unsupported format version {}.{}",
+                    major, minor
+                )))
+            }
+        };
+
+        if major == 1 && header_len > MAX_HEADER_LEN {
+            return Err(format_err(format!(
+                "header length {} exceeds the maximum of {}",
+                header_len, MAX_HEADER_LEN
+            )));
+        }
+
+        // Grows the buffer only as data arrives instead of allocating the stated length up front.
+        let mut header = Vec::with_capacity(header_len.min(MAX_HEADER_LEN));
+        reader
+            .by_ref()
+            .take(header_len as u64)
+            .read_to_end(&mut header)?;
+        if header.len() != header_len {
+            return Err(format_err("header is truncated"));
+        }
+        let header = if major == 3 {
+            String::from_utf8(header).map_err(|_| format_err("header is not valid UTF-8"))?
+        } else {
+            header.into_iter().map(char::from).collect()
+        };
+
+        Self::parse(py, &header)
+    }
+
+    fn parse(py: Python<'py>, header: &str) -> PyResult<Self> {
+        let entries = match Literal::parse(header)? {
+            Literal::Dict(entries) => entries,
+            _ => return Err(format_err("header is not a dictionary")),
+        };
+
+        let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+        for (key, value) in entries {
+            match (key, value) {
+                (Literal::Str(key), value) if key == "descr" => {
+                    descr = Some(PyArrayDescr::new(py, &value.to_object(py)?)?);
+                }
+                (Literal::Str(key), Literal::Bool(value)) if key == "fortran_order" => {
+                    fortran_order = Some(value);
+                }
+                (Literal::Str(key), Literal::Tuple(values)) if key == "shape" => {
+                    let values = values
+                        .into_iter()
+                        .map(|value| match value {
+                            Literal::Int(value) if value >= 0 => Ok(value as usize),
+                            _ => Err(format_err("shape must be a tuple of non-negative integers")),
+                        })
+                        .collect::<PyResult<_>>()?;
+                    shape = Some(values);
+                }
+                (key, _) => {
+                    return Err(format_err(format!("unexpected header entry {:?}", key)));
+                }
+            }
+        }
+
+        Ok(Self {
+            descr: descr.ok_or_else(|| format_err("missing 'descr' key"))?,
+            fortran_order: fortran_order
+                .ok_or_else(|| format_err("missing 'fortran_order' key"))?,
+            shape: shape.ok_or_else(|| format_err("missing 'shape' key"))?,
+        })
+    }
+}
+
+fn write_header<W: Write>(
+    writer: &mut W,
+    dtype: &PyArrayDescr,
+    fortran_order: bool,
+    shape: &[usize],
+) -> PyResult<()> {
+    // Structured types are described by a list of fields, all others by their type string.
+    let descr = if dtype.has_fields() {
+        dtype.getattr("descr")?.repr()?
+    } else {
+        dtype.getattr("str")?.repr()?
+    };
+
+    let mut header = format!(
+        "{{'descr': {}, 'fortran_order': {}, 'shape': (",
+        descr.to_str()?,
+        if fortran_order { "True" } else { "False" },
+    );
+    for dim in shape {
+        header.push_str(&format!("{},", dim));
+        if shape.len() > 1 {
+            header.push(' ');
+        }
+    }
+    if shape.len() > 1 {
+        header.pop();
+        header.pop();
+    }
+    header.push_str("), }");
+
+    // Version 3.0 is only required if the header cannot be encoded as Latin-1.
+    let (header, major) = if header.chars().all(|c| (c as u32) < 256) {
+        (header.chars().map(|c| c as u8).collect::<Vec<_>>(), 1)
+    } else {
+        (header.into_bytes(), 3)
+    };
+
+    let unpadded_len = |len_size: usize| MAGIC.len() + 2 + len_size + header.len() + 1;
+    let (major, len_size) = if major == 1 && unpadded_len(2) + ALIGNMENT <= u16::MAX as usize {
+        (1, 2)
+    } else {
+        (major.max(2), 4)
+    };
+    let padding = (ALIGNMENT - unpadded_len(len_size) % ALIGNMENT) % ALIGNMENT;
+    let header_len = header.len() + padding + 1;
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[major, 0])?;
+    if len_size == 2 {
+        writer.write_all(&(header_len as u16).to_le_bytes())?;
+    } else if header_len > u32::MAX as usize {
+        return Err(PyValueError::new_err("NPY header is too large"));
+    } else {
+        writer.write_all(&(header_len as u32).to_le_bytes())?;
+    }
+    writer.write_all(&header)?;
+    writer.write_all(&vec![b' '; padding])?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// The subset of Python literals which can appear in the header of an NPY file.
+#[derive(Debug, PartialEq)]
+enum Literal {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+    None,
+    Tuple(Vec<Literal>),
+    List(Vec<Literal>),
+    Dict(Vec<(Literal, Literal)>),
+}
+
+impl Literal {
+    fn parse(input: &str) -> PyResult<Self> {
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            pos: 0,
+        };
+        let literal = parser.parse_literal()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.input.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+        Ok(literal)
+    }
+
+    fn to_object(&self, py: Python) -> PyResult<PyObject> {
+        let to_objects = |values: &[Literal]| {
+            values
+                .iter()
+                .map(|value| value.to_object(py))
+                .collect::<PyResult<Vec<_>>>()
+        };
+        let object = match self {
+            Literal::Str(value) => value.to_object(py),
+            Literal::Int(value) => value.to_object(py),
+            Literal::Bool(value) => value.to_object(py),
+            Literal::None => py.None(),
+            Literal::Tuple(values) => PyTuple::new(py, to_objects(values)?).to_object(py),
+            Literal::List(values) => PyList::new(py, to_objects(values)?).to_object(py),
+            Literal::Dict(entries) => {
+                let dict = PyDict::new(py);
+                for (key, value) in entries {
+                    dict.set_item(key.to_object(py)?, value.to_object(py)?)?;
+                }
+                dict.to_object(py)
+            }
+        };
+        Ok(object)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, msg: &str) -> PyErr {
+        format_err(format!("{} at position {} of header", msg, self.pos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> PyResult<()> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c as char)))
+        }
+    }
+
+    fn parse_literal(&mut self) -> PyResult<Literal> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'\'') | Some(b'"') => self.parse_str().map(Literal::Str),
+            Some(b'(') => {
+                let (values, trailing_comma) = self.parse_sequence(b'(', b')')?;
+                // A parenthesized expression without a comma is not a tuple.
+                if values.len() == 1 && !trailing_comma {
+                    Ok(values.into_iter().next().unwrap())
+                } else {
+                    Ok(Literal::Tuple(values))
+                }
+            }
+            Some(b'[') => Ok(Literal::List(self.parse_sequence(b'[', b']')?.0)),
+            Some(b'{') => self.parse_dict(),
+            Some(b'-') | Some(b'+') | Some(b'0'..=b'9') => self.parse_int(),
+            Some(b'A'..=b'Z') | Some(b'a'..=b'z') => {
+                let start = self.pos;
+                while let Some(b'A'..=b'Z') | Some(b'a'..=b'z') = self.peek() {
+                    self.pos += 1;
+                }
+                match &self.input[start..self.pos] {
+                    b"True" => Ok(Literal::Bool(true)),
+                    b"False" => Ok(Literal::Bool(false)),
+                    b"None" => Ok(Literal::None),
+                    _ => {
+                        self.pos = start;
+                        Err(self.error("unexpected identifier"))
+                    }
+                }
+            }
+            _ => Err(self.error("expected a literal")),
+        }
+    }
+
+    fn parse_sequence(&mut self, open: u8, close: u8) -> PyResult<(Vec<Literal>, bool)> {
+        self.expect(open)?;
+        let mut values = Vec::new();
+        let mut trailing_comma = false;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.pos += 1;
+                return Ok((values, trailing_comma));
+            }
+            values.push(self.parse_literal()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                    trailing_comma = true;
+                }
+                Some(c) if c == close => trailing_comma = false,
+                _ => return Err(self.error(&format!("expected ',' or '{}'", close as char))),
+            }
+        }
+    }
+
+    fn parse_dict(&mut self) -> PyResult<Literal> {
+        self.expect(b'{')?;
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b'}') {
+                self.pos += 1;
+                return Ok(Literal::Dict(entries));
+            }
+            let key = self.parse_literal()?;
+            self.expect(b':')?;
+            let value = self.parse_literal()?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => (),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn parse_int(&mut self) -> PyResult<Literal> {
+        let start = self.pos;
+        if let Some(b'-') | Some(b'+') = self.peek() {
+            self.pos += 1;
+        }
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        let digits = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        let value = digits.parse().map_err(|_| self.error("invalid integer"))?;
+        // Files written by Python 2 can contain long integers like `3L`.
+        if let Some(b'L') | Some(b'l') = self.peek() {
+            self.pos += 1;
+        }
+        Ok(Literal::Int(value))
+    }
+
+    fn parse_str(&mut self) -> PyResult<String> {
+        let quote = self.peek().unwrap();
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'n') => '\n',
+                        Some(b't') => '\t',
+                        Some(b'r') => '\r',
+                        Some(b'0') => '\0',
+                        Some(c @ b'\\') | Some(c @ b'\'') | Some(c @ b'"') => c as char,
+                        Some(b'x') => self.parse_hex_escape(2)?,
+                        Some(b'u') => self.parse_hex_escape(4)?,
+                        Some(b'U') => self.parse_hex_escape(8)?,
+                        _ => return Err(self.error("unsupported escape sequence")),
+                    };
+                    self.pos += 1;
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buf).as_bytes());
+                }
+                Some(c) => {
+                    self.pos += 1;
+                    bytes.push(c);
+                }
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("invalid string"))
+    }
+
+    fn parse_hex_escape(&mut self, len: usize) -> PyResult<char> {
+        let digits = self
+            .input
+            .get(self.pos + 1..self.pos + 1 + len)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .ok_or_else(|| self.error("truncated escape sequence"))?;
+        let value = u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .ok_or_else(|| self.error("invalid escape sequence"))?;
+        self.pos += len;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Literal::{self, *};
+
+    fn str(value: &str) -> Literal {
+        Str(value.to_owned())
+    }
+
+    #[test]
+    fn parse_header() {
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (3, 4), }          \n";
+        assert_eq!(
+            Literal::parse(header).unwrap(),
+            Dict(vec![
+                (str("descr"), str("<f8")),
+                (str("fortran_order"), Bool(false)),
+                (str("shape"), Tuple(vec![Int(3), Int(4)])),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_tuples() {
+        assert_eq!(Literal::parse("()").unwrap(), Tuple(vec![]));
+        assert_eq!(Literal::parse("(3,)").unwrap(), Tuple(vec![Int(3)]));
+        assert_eq!(
+            Literal::parse("(3L, 2L)").unwrap(),
+            Tuple(vec![Int(3), Int(2)])
+        );
+        assert_eq!(Literal::parse("(3)").unwrap(), Int(3));
+    }
+
+    #[test]
+    fn parse_structured_descr() {
+        let descr = r#"[('x', '<i4'), ("yé", '<f8', (2,)), (('title', 'z'), '|b1')]"#;
+        assert_eq!(
+            Literal::parse(descr).unwrap(),
+            List(vec![
+                Tuple(vec![str("x"), str("<i4")]),
+                Tuple(vec![str("yé"), str("<f8"), Tuple(vec![Int(2)])]),
+                Tuple(vec![Tuple(vec![str("title"), str("z")]), str("|b1")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Literal::parse("{'descr': '<f8'").is_err());
+        assert!(Literal::parse("{'descr': <f8}").is_err());
+        assert!(Literal::parse("(1, 2) 3").is_err());
+        assert!(Literal::parse("'unterminated").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Reading and writing `.npz` archives, i.e. zip files of NPY files
+//! as produced by `np.savez` and `np.savez_compressed`.
+use std::io::{Read, Seek, Write};
+
+use ndarray::Dimension;
+use pyo3::{
+    exceptions::{PyIOError, PyKeyError},
+    PyErr, PyResult, Python,
+};
+use zip::{result::ZipError, write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{read_npy, write_npy};
+use crate::dtype::Element;
+use crate::PyArray;
+
+fn zip_err(err: ZipError) -> PyErr {
+    match err {
+        ZipError::Io(err) => err.into(),
+        err => PyIOError::new_err(err.to_string()),
+    }
+}
+
+/// Reads the arrays stored in an `.npz` archive.
+///
+/// # Example
+/// ```
+/// use numpy::io::npz::{NpzReader, NpzWriter};
+/// use numpy::PyArray1;
+/// pyo3::Python::with_gil(|py| {
+///     let mut writer = NpzWriter::new(std::io::Cursor::new(Vec::new()));
+///     writer.add_array("a", PyArray1::from_slice(py, &[1, 2, 3])).unwrap();
+///     let file = writer.finish().unwrap();
+///
+///     let mut reader = NpzReader::new(file).unwrap();
+///     assert_eq!(reader.names(), ["a"]);
+///     let a: &PyArray1<i32> = reader.by_name(py, "a").unwrap();
+///     assert_eq!(a.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3]);
+/// });
+/// ```
+pub struct NpzReader<R: Read + Seek> {
+    archive: ZipArchive<R>,
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+    /// Opens the archive stored in `reader`.
+    pub fn new(reader: R) -> PyResult<Self> {
+        let archive = ZipArchive::new(reader).map_err(zip_err)?;
+        Ok(Self { archive })
+    }
+
+    /// Returns the names of the arrays stored in the archive.
+    pub fn names(&self) -> Vec<&str> {
+        self.archive
+            .file_names()
+            .map(|name| name.strip_suffix(".npy").unwrap_or(name))
+            .collect()
+    }
+
+    /// Returns the number of arrays stored in the archive.
+    pub fn len(&self) -> usize {
+        self.archive.len()
+    }
+
+    /// Returns whether the archive is empty.
+    pub fn is_empty(&self) -> bool {
+        self.archive.len() == 0
+    }
+
+    /// Reads the array called `name`.
+    ///
+    /// Like `np.load`, this accepts the name both with and without the `.npy` extension.
+    pub fn by_name<'py, T, D>(
+        &mut self,
+        py: Python<'py>,
+        name: &str,
+    ) -> PyResult<&'py PyArray<T, D>>
+    where
+        T: Element,
+        D: Dimension,
+    {
+        let file_name = if self.archive.file_names().any(|file| file == name) {
+            name.to_owned()
+        } else {
+            format!("{}.npy", name)
+        };
+        let file = match self.archive.by_name(&file_name) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => {
+                return Err(PyKeyError::new_err(format!(
+                    "{} is not a file in the archive",
+                    name
+                )))
+            }
+            Err(err) => return Err(zip_err(err)),
+        };
+        read_npy(py, file)
+    }
+}
+
+/// Writes arrays into an `.npz` archive.
+///
+/// See [`NpzReader`] for an example.
+pub struct NpzWriter<W: Write + Seek> {
+    zip: ZipWriter<W>,
+    options: FileOptions,
+}
+
+impl<W: Write + Seek> NpzWriter<W> {
+    /// Creates an uncompressed archive like `np.savez`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            zip: ZipWriter::new(writer),
+            options: FileOptions::default().compression_method(CompressionMethod::Stored),
+        }
+    }
+
+    /// Creates a compressed archive like `np.savez_compressed`.
+    pub fn new_compressed(writer: W) -> Self {
+        Self {
+            zip: ZipWriter::new(writer),
+            options: FileOptions::default().compression_method(CompressionMethod::Deflated),
+        }
+    }
+
+    /// Adds `array` to the archive under `name`.
+    ///
+    /// The `.npy` extension is appended to `name` as done by `np.savez`.
+    pub fn add_array<T, D>(&mut self, name: &str, array: &PyArray<T, D>) -> PyResult<()>
+    where
+        T: Element,
+        D: Dimension,
+    {
+        self.zip
+            .start_file(format!("{}.npy", name), self.options)
+            .map_err(zip_err)?;
+        write_npy(&mut self.zip, array)
+    }
+
+    /// Writes the central directory of the archive and returns the underlying writer.
+    pub fn finish(mut self) -> PyResult<W> {
+        self.zip.finish().map_err(zip_err)
+    }
+}
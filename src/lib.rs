@@ -21,7 +21,7 @@
 //!     pyo3::Python::with_gil(|py| {
 //!         let py_array = array![[1i64, 2], [3, 4]].to_pyarray(py);
 //!         assert_eq!(
-//!             py_array.readonly().as_array(),
+//!             py_array.readonly().unwrap().as_array(),
 //!             array![[1i64, 2], [3, 4]]
 //!         );
 //!     })
@@ -30,14 +30,25 @@
 #![allow(clippy::needless_lifetimes)] // We often want to make the GIL lifetime explicit.
 
 pub mod array;
+mod array_like;
+mod buffer;
+mod byteorder;
 pub mod convert;
+pub mod dlpack;
 mod dtype;
 mod error;
+pub mod io;
+mod masked;
+#[cfg(feature = "memmap")]
+mod mmap;
 pub mod npyffi;
 pub mod npyiter;
 mod readonly;
+mod scalar;
 mod slice_container;
+pub mod sparse;
 mod sum_products;
+mod unaligned;
 
 pub use ndarray;
 
@@ -45,18 +56,36 @@ pub use crate::array::{
     get_array_module, PyArray, PyArray0, PyArray1, PyArray2, PyArray3, PyArray4, PyArray5,
     PyArray6, PyArrayDyn,
 };
+pub use crate::array_like::{PyArrayLike, PyArrayLike1, PyArrayLike2, PyArrayLikeDyn};
+pub use crate::byteorder::{ByteSwap, NonNative};
 pub use crate::convert::{IntoPyArray, NpyIndex, ToNpyDims, ToPyArray};
-pub use crate::dtype::{dtype, Complex32, Complex64, Element, PyArrayDescr};
-pub use crate::error::{DimensionalityError, FromVecError, NotContiguousError, TypeError};
+pub use crate::dtype::{
+    dtype, ByteOrder, Casting, Complex32, Complex64, DatetimeUnit, Element, PyArrayDescr,
+};
+pub use crate::error::{
+    AsSliceError, DimensionalityError, FromVecError, NotAlignedError, NotContiguousError, TypeError,
+};
+pub use crate::masked::{PyMaskedArray, PyMaskedArray1, PyMaskedArray2, PyMaskedArrayDyn};
+#[cfg(feature = "memmap")]
+pub use crate::mmap::MmapOwner;
 pub use crate::npyffi::{PY_ARRAY_API, PY_UFUNC_API};
+#[cfg(feature = "rayon")]
+pub use crate::npyiter::NpyParIter;
 pub use crate::npyiter::{
-    IterMode, NpyIterFlag, NpyMultiIter, NpyMultiIterBuilder, NpySingleIter, NpySingleIterBuilder,
+    IterMode, NpyIterFlag, NpyIterPart, NpyMultiChunkIter, NpyMultiIter, NpyMultiIterBuilder,
+    NpyMultiLendingChunkIter, NpyMultiLendingIter, NpySingleChunkIter, NpySingleIter,
+    NpySingleIterBuilder, NpySingleLendingChunkIter, NpySingleLendingIter,
 };
 pub use crate::readonly::{
     PyReadonlyArray, PyReadonlyArray1, PyReadonlyArray2, PyReadonlyArray3, PyReadonlyArray4,
     PyReadonlyArray5, PyReadonlyArray6, PyReadonlyArrayDyn,
 };
-pub use crate::sum_products::{dot, einsum_impl, inner};
+pub use crate::scalar::PyScalar;
+pub use crate::sum_products::{
+    dot, einsum_impl, inner, kron, matmul, matmul_into, outer, tensordot, trace, vdot, Einsum,
+    MatmulDim,
+};
+pub use crate::unaligned::Unaligned;
 pub use ndarray::{array, Ix1, Ix2, Ix3, Ix4, Ix5, Ix6, IxDyn};
 
 #[cfg(doctest)]
@@ -79,7 +108,7 @@ mod doctest {
 /// pyo3::Python::with_gil(|py| {
 ///     let array = numpy::pyarray![py, [1, 2], [3, 4]];
 ///     assert_eq!(
-///         array.readonly().as_array(),
+///         array.readonly().unwrap().as_array(),
 ///         ndarray::array![[1, 2], [3, 4]]
 ///     );
 /// });
@@ -0,0 +1,137 @@
+//! Support for masked arrays of the `numpy.ma` module
+use ndarray::{ArrayView, Dimension};
+use pyo3::{
+    ffi,
+    types::{PyDict, PyModule},
+    AsPyPointer, FromPyObject, IntoPy, PyAny, PyDowncastError, PyErr, PyObject, PyResult, Python,
+};
+
+use crate::dtype::Element;
+use crate::{PyArray, PyReadonlyArray};
+
+/// A masked array of the `numpy.ma` module, split into its data and a boolean mask
+/// which is `true` for invalid, i.e. masked, elements.
+///
+/// Masks which are `nomask` or scalars are broadcast to the shape of the data without
+/// copying, so that the mask always has the same shape as the data.
+/// Both data and mask are borrowed as [`PyReadonlyArray`] while this wrapper exists.
+///
+/// # Example
+/// ```
+/// use numpy::PyMaskedArray1;
+/// use pyo3::types::IntoPyDict;
+/// pyo3::Python::with_gil(|py| {
+///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+///     let masked: PyMaskedArray1<f64> = py
+///         .eval("np.ma.masked_invalid([1.0, np.nan, 3.0])", None, Some(locals))
+///         .unwrap()
+///         .extract()
+///         .unwrap();
+///     let valid: f64 = masked.iter().flatten().sum();
+///     assert_eq!(valid, 4.0);
+///
+///     let (data, mask) = masked.as_array_with_mask();
+///     assert_eq!(data[0], 1.0);
+///     assert_eq!(mask, ndarray::array![false, true, false]);
+/// });
+/// ```
+pub struct PyMaskedArray<'py, T, D> {
+    array: &'py PyAny,
+    data: PyReadonlyArray<'py, T, D>,
+    mask: PyReadonlyArray<'py, bool, D>,
+}
+
+/// One-dimensional masked arrays.
+pub type PyMaskedArray1<'py, T> = PyMaskedArray<'py, T, ndarray::Ix1>;
+/// Two-dimensional masked arrays.
+pub type PyMaskedArray2<'py, T> = PyMaskedArray<'py, T, ndarray::Ix2>;
+/// Dynamic-dimensional masked arrays.
+pub type PyMaskedArrayDyn<'py, T> = PyMaskedArray<'py, T, ndarray::IxDyn>;
+
+impl<'py, T: Element, D: Dimension> PyMaskedArray<'py, T, D> {
+    /// Creates a new `numpy.ma.MaskedArray` sharing the given data and mask.
+    ///
+    /// Returns an error if the shapes of `data` and `mask` differ.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::{PyArray, PyMaskedArray};
+    /// use pyo3::IntoPy;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let data = PyArray::from_slice(py, &[1, 2, 3]);
+    ///     let mask = PyArray::from_slice(py, &[false, false, true]);
+    ///     let masked = PyMaskedArray::new(data, mask).unwrap().into_py(py);
+    ///     pyo3::py_run!(py, masked, "assert masked.sum() == 3 and masked.mask[2]");
+    /// });
+    /// ```
+    pub fn new(data: &'py PyArray<T, D>, mask: &'py PyArray<bool, D>) -> PyResult<Self> {
+        let py = data.py();
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("mask", mask)?;
+        kwargs.set_item("copy", false)?;
+        ma_module(py)?
+            .getattr("MaskedArray")?
+            .call((data,), Some(kwargs))?
+            .extract()
+    }
+
+    /// Returns the underlying `numpy.ma.MaskedArray` object.
+    pub fn as_any(&self) -> &'py PyAny {
+        self.array
+    }
+
+    /// Returns the data of the masked array, including the values of masked elements.
+    pub fn data(&self) -> &PyReadonlyArray<'py, T, D> {
+        &self.data
+    }
+
+    /// Returns the mask of the masked array which has the same shape as the data.
+    pub fn mask(&self) -> &PyReadonlyArray<'py, bool, D> {
+        &self.mask
+    }
+
+    /// Returns immutable views of the data and the mask.
+    pub fn as_array_with_mask(&self) -> (ArrayView<'_, T, D>, ArrayView<'_, bool, D>) {
+        (self.data.as_array(), self.mask.as_array())
+    }
+
+    /// Iterates over all elements in logical order, yielding `None` for masked elements.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&T>> + '_ {
+        let (data, mask) = self.as_array_with_mask();
+        data.into_iter()
+            .zip(mask)
+            .map(|(value, &masked)| if masked { None } else { Some(value) })
+    }
+}
+
+impl<'py, T: Element, D: Dimension> FromPyObject<'py> for PyMaskedArray<'py, T, D> {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let py = ob.py();
+        let masked_array = ma_module(py)?.getattr("MaskedArray")?;
+        match unsafe { ffi::PyObject_IsInstance(ob.as_ptr(), masked_array.as_ptr()) } {
+            -1 => return Err(PyErr::fetch(py)),
+            0 => return Err(PyDowncastError::new(ob, "MaskedArray").into()),
+            _ => (),
+        }
+        let data: &PyArray<T, D> = ob.getattr("data")?.extract()?;
+        let mask: &PyArray<bool, D> = PyModule::import(py, "numpy")?
+            .getattr("broadcast_to")?
+            .call1((ob.getattr("mask")?, data.shape().to_vec()))?
+            .extract()?;
+        Ok(Self {
+            array: ob,
+            data: data.readonly()?,
+            mask: mask.readonly()?,
+        })
+    }
+}
+
+impl<'py, T, D> IntoPy<PyObject> for PyMaskedArray<'py, T, D> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.array.into_py(py)
+    }
+}
+
+fn ma_module(py: Python) -> PyResult<&PyModule> {
+    PyModule::import(py, "numpy.ma")
+}
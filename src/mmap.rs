@@ -0,0 +1,150 @@
+//! Support for arrays backed by memory-mapped files
+use std::mem;
+
+use memmap2::{Mmap, MmapMut};
+use ndarray::{Dimension, IntoDimension};
+use pyo3::{exceptions::PyValueError, PyResult, Python};
+
+use crate::convert::NpyStrides;
+use crate::dtype::Element;
+use crate::npyffi;
+use crate::slice_container::SliceOwner;
+use crate::PyArray;
+
+/// Memory maps which can be used as the base object of a [`PyArray`].
+///
+/// This is implemented for [`Mmap`], which produces readonly arrays,
+/// and [`MmapMut`], which produces writeable arrays.
+pub trait MmapOwner: sealed::Sealed + Send + 'static {
+    /// Whether arrays backed by this mapping can be written to.
+    const WRITEABLE: bool;
+
+    #[doc(hidden)]
+    fn data_ptr(&mut self) -> *mut u8;
+
+    #[doc(hidden)]
+    fn byte_len(&self) -> usize;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Mmap {}
+    impl Sealed for super::MmapMut {}
+}
+
+impl MmapOwner for Mmap {
+    const WRITEABLE: bool = false;
+
+    fn data_ptr(&mut self) -> *mut u8 {
+        self.as_ptr() as *mut u8
+    }
+
+    fn byte_len(&self) -> usize {
+        (**self).len()
+    }
+}
+
+impl MmapOwner for MmapMut {
+    const WRITEABLE: bool = true;
+
+    fn data_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        (**self).len()
+    }
+}
+
+impl<T: Element, D: Dimension> PyArray<T, D> {
+    /// Creates a C-order array of shape `dims` whose elements are stored in `mmap`
+    /// starting at byte `offset`.
+    ///
+    /// The mapping is moved into the base object of the returned array and unmapped
+    /// when the array is garbage collected. Arrays backed by a readonly [`Mmap`] are not writeable.
+    ///
+    /// This method returns an error if `T` is an object type, if the mapping is too short
+    /// to hold an array of the given shape or if `offset` is not suitably aligned for `T`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::io::Write;
+    /// use numpy::PyArray2;
+    ///
+    /// let path = std::env::temp_dir().join(format!("numpy-from-mmap-{}.bin", std::process::id()));
+    /// let mut file = std::fs::File::create(&path).unwrap();
+    /// for value in 0..6_i32 {
+    ///     file.write_all(&value.to_ne_bytes()).unwrap();
+    /// }
+    /// let file = std::fs::File::open(&path).unwrap();
+    /// let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+    ///
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = PyArray2::<i32>::from_mmap(py, mmap, [2, 3], 0).unwrap();
+    ///     assert_eq!(array.readonly().unwrap().as_array(), ndarray::array![[0, 1, 2], [3, 4, 5]]);
+    ///     pyo3::py_run!(py, array, "assert not array.flags['WRITEABLE']");
+    /// });
+    /// # let _ = std::fs::remove_file(&path);
+    /// ```
+    pub fn from_mmap<'py, M, ID>(
+        py: Python<'py>,
+        mmap: M,
+        dims: ID,
+        offset: usize,
+    ) -> PyResult<&'py Self>
+    where
+        M: MmapOwner,
+        ID: IntoDimension<Dim = D>,
+    {
+        if !T::IS_COPY {
+            return Err(PyValueError::new_err(
+                "cannot create an array of objects from a memory map",
+            ));
+        }
+
+        let dims = dims.into_dimension();
+        let size = dims
+            .size_checked()
+            .and_then(|len| len.checked_mul(mem::size_of::<T>()))
+            .and_then(|size| size.checked_add(offset));
+        match size {
+            Some(size) if size <= mmap.byte_len() => (),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "memory map of {} bytes is too short for an array of shape {:?} at offset {}",
+                    mmap.byte_len(),
+                    dims.slice(),
+                    offset
+                )))
+            }
+        }
+
+        let mut mmap = Box::new(mmap);
+        let data_ptr = unsafe { mmap.data_ptr().add(offset) };
+        if data_ptr as usize % mem::align_of::<T>() != 0 {
+            return Err(PyValueError::new_err(format!(
+                "offset {} is not aligned to {} bytes",
+                offset,
+                mem::align_of::<T>()
+            )));
+        }
+
+        let strides = NpyStrides::from_dim(&dims, mem::size_of::<T>());
+        let flag = if M::WRITEABLE {
+            npyffi::NPY_ARRAY_WRITEABLE
+        } else {
+            0
+        };
+        Ok(unsafe {
+            PyArray::from_raw_parts_with_flag(
+                py,
+                dims,
+                strides.as_ptr(),
+                data_ptr as *const T,
+                flag,
+                SliceOwner(mmap),
+            )
+        })
+    }
+}
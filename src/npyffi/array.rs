@@ -25,7 +25,7 @@ const CAPSULE_NAME: &str = "_ARRAY_API";
 ///     unsafe {
 ///         PY_ARRAY_API.PyArray_Sort(array.as_array_ptr(), 0, NPY_SORTKIND::NPY_QUICKSORT);
 ///     }
-///     assert_eq!(array.readonly().as_slice().unwrap(), &[2, 3, 4]);
+///     assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[2, 3, 4]);
 /// })
 /// ```
 pub static PY_ARRAY_API: PyArrayAPI = PyArrayAPI::new();
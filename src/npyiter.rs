@@ -3,22 +3,38 @@
 //! This module exposes two iterators:
 //! [NpySingleIter](./struct.NpySingleIter.html) and
 //! [NpyMultiIter](./struct.NpyMultiIter.html).
+//!
+//! Both builders can alternatively create iterators using NumPy's external loop
+//! which yield whole inner loops as one-dimensional array views instead of single elements,
+//! c.f. [NpySingleChunkIter](./struct.NpySingleChunkIter.html) and
+//! [NpyMultiChunkIter](./struct.NpyMultiChunkIter.html).
+//!
+//! Buffered iterators, e.g. those casting elements, cannot implement [`Iterator`] as their items
+//! point into buffers which are reused when advancing. They are created using `build_lending`
+//! and lend each item only until the next one is requested, c.f.
+//! [NpySingleLendingIter](./struct.NpySingleLendingIter.html) and
+//! [NpyMultiLendingIter](./struct.NpyMultiLendingIter.html).
 use crate::npyffi::{
     array::PY_ARRAY_API,
     npy_intp, npy_uint32,
-    objects::{NpyIter, PyArrayObject},
-    types::{NPY_CASTING, NPY_ORDER},
-    NPY_ARRAY_WRITEABLE, NPY_ITER_BUFFERED, NPY_ITER_COMMON_DTYPE, NPY_ITER_COPY_IF_OVERLAP,
-    NPY_ITER_DELAY_BUFALLOC, NPY_ITER_DONT_NEGATE_STRIDES, NPY_ITER_GROWINNER, NPY_ITER_RANGED,
-    NPY_ITER_READONLY, NPY_ITER_READWRITE, NPY_ITER_REDUCE_OK, NPY_ITER_REFS_OK,
-    NPY_ITER_ZEROSIZE_OK,
+    objects::{NpyIter, NpyIter_GetMultiIndexFunc, PyArrayObject},
+    types::NPY_ORDER,
+    NPY_ARRAY_WRITEABLE, NPY_ITER_ALLOCATE, NPY_ITER_BUFFERED, NPY_ITER_COMMON_DTYPE,
+    NPY_ITER_COPY_IF_OVERLAP, NPY_ITER_C_INDEX, NPY_ITER_DELAY_BUFALLOC,
+    NPY_ITER_DONT_NEGATE_STRIDES, NPY_ITER_EXTERNAL_LOOP, NPY_ITER_F_INDEX, NPY_ITER_GROWINNER,
+    NPY_ITER_MULTI_INDEX, NPY_ITER_RANGED, NPY_ITER_READONLY, NPY_ITER_READWRITE,
+    NPY_ITER_REDUCE_OK, NPY_ITER_REFS_OK, NPY_ITER_ZEROSIZE_OK,
 };
-use crate::{Element, PyArray, PyArrayDyn, PyReadonlyArray};
-use pyo3::{prelude::*, PyNativeType};
+use crate::{Casting, Element, NotAlignedError, PyArray, PyArrayDescr, PyReadonlyArray};
+use ndarray::{ArrayView1, ArrayViewMut1, Axis, Dimension, Ix1, IxDyn, ShapeBuilder};
+use pyo3::exceptions::PyValueError;
+use pyo3::{prelude::*, AsPyPointer};
 
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::os::raw::*;
 use std::ptr;
+use std::sync::Arc;
 
 /// Flags for constructing an iterator.
 /// For the meanings of each flag, readers can refer to [the numpy document][doc].
@@ -29,15 +45,15 @@ use std::ptr;
 /// [doc]: https://numpy.org/doc/stable/reference/c-api/iterator.html#c.NpyIter_MultiNew
 /// [issue]: https://github.com/PyO3/rust-numpy/issues
 // Here's a list of unsupported flags:
-// CIndex,
-// FIndex,
-// MultiIndex,
-// ExternalLoop,
+// ExternalLoop (use `build_chunked` instead),
 // ReadWrite,
 // ReadOnly,
 // WriteOnly,
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum NpyIterFlag {
+    CIndex,
+    FIndex,
+    MultiIndex,
     CommonDtype,
     RefsOk,
     ZerosizeOk,
@@ -54,6 +70,9 @@ impl NpyIterFlag {
     fn to_c_enum(self) -> npy_uint32 {
         use NpyIterFlag::*;
         match self {
+            CIndex => NPY_ITER_C_INDEX,
+            FIndex => NPY_ITER_F_INDEX,
+            MultiIndex => NPY_ITER_MULTI_INDEX,
             CommonDtype => NPY_ITER_COMMON_DTYPE,
             RefsOk => NPY_ITER_REFS_OK,
             ZerosizeOk => NPY_ITER_ZEROSIZE_OK,
@@ -86,77 +105,220 @@ mod itermode {
         };
     }
 
-    /// A combinator type that represents the mode of an iterator
-    /// (E.g., Readonly, ReadWrite, Readonly + ReadWrite).
-    pub trait MultiIterMode {
+    /// The access mode and element type of a single operand of a multi-iterator,
+    /// i.e. [RO](./struct.RO.html), [RW](./struct.RW.html) or [WO](./struct.WO.html).
+    pub trait IterOperand {
         private_decl!();
-        type Pre: MultiIterMode;
-        const FLAG: npy_uint32 = 0;
-        fn flags() -> Vec<npy_uint32> {
-            if Self::FLAG == 0 {
-                vec![]
-            } else {
-                let mut res = Self::Pre::flags();
-                res.push(Self::FLAG);
-                res
-            }
-        }
+        /// The element type of the operand.
+        type Elem: Element;
+        /// The operand flag passed to NumPy.
+        const FLAG: npy_uint32;
     }
 
-    impl MultiIterMode for () {
-        private_impl!();
-        type Pre = ();
+    /// Conversion of the data pointers of an operand into the items of an iterator.
+    pub trait OperandItem<'py>: IterOperand {
+        /// The type of a single element, e.g. `&'py T` or `&'py mut T`.
+        type Item;
+        /// The type of an inner loop, e.g. `ArrayView1<'py, T>` or `ArrayViewMut1<'py, T>`.
+        type Chunk;
+        #[doc(hidden)]
+        unsafe fn item(ptr: *mut c_char) -> Self::Item;
+        #[doc(hidden)]
+        unsafe fn chunk(chunk: (*mut c_char, npy_intp, usize)) -> Self::Chunk;
     }
 
-    /// Represents the iterator mode where the last array is readonly.
-    pub struct RO<S: MultiIterMode>(PhantomData<S>);
+    /// Represents a readonly operand with elements of type `T`.
+    pub struct RO<T>(PhantomData<T>);
 
-    /// Represents the iterator mode where the last array is readwrite.
-    pub struct RW<S: MultiIterMode>(PhantomData<S>);
+    /// Represents a readwrite operand with elements of type `T`.
+    pub struct RW<T>(PhantomData<T>);
 
-    impl<S: MultiIterMode> MultiIterMode for RO<S> {
+    /// Represents an output operand with elements of type `T` which is allocated by the iterator.
+    pub struct WO<T>(PhantomData<T>);
+
+    impl<T: Element> IterOperand for RO<T> {
         private_impl!();
-        type Pre = S;
+        type Elem = T;
         const FLAG: npy_uint32 = NPY_ITER_READONLY;
     }
 
-    impl<S: MultiIterMode> MultiIterMode for RW<S> {
+    impl<T: Element> IterOperand for RW<T> {
         private_impl!();
-        type Pre = S;
+        type Elem = T;
         const FLAG: npy_uint32 = NPY_ITER_READWRITE;
     }
 
+    impl<T: Element> IterOperand for WO<T> {
+        private_impl!();
+        type Elem = T;
+        // Allocated outputs are initialized and hence also readable which is required for reductions.
+        const FLAG: npy_uint32 = NPY_ITER_READWRITE | NPY_ITER_ALLOCATE;
+    }
+
+    impl<'py, T: Element + 'py> OperandItem<'py> for RO<T> {
+        type Item = &'py T;
+        type Chunk = ArrayView1<'py, T>;
+
+        unsafe fn item(ptr: *mut c_char) -> Self::Item {
+            &*(ptr as *const T)
+        }
+
+        unsafe fn chunk((ptr, stride, len): (*mut c_char, npy_intp, usize)) -> Self::Chunk {
+            chunk_view((ptr as *mut T, stride, len))
+        }
+    }
+
+    impl<'py, T: Element + 'py> OperandItem<'py> for RW<T> {
+        type Item = &'py mut T;
+        type Chunk = ArrayViewMut1<'py, T>;
+
+        unsafe fn item(ptr: *mut c_char) -> Self::Item {
+            &mut *(ptr as *mut T)
+        }
+
+        unsafe fn chunk((ptr, stride, len): (*mut c_char, npy_intp, usize)) -> Self::Chunk {
+            chunk_view_mut((ptr as *mut T, stride, len))
+        }
+    }
+
+    impl<'py, T: Element + 'py> OperandItem<'py> for WO<T> {
+        type Item = &'py mut T;
+        type Chunk = ArrayViewMut1<'py, T>;
+
+        unsafe fn item(ptr: *mut c_char) -> Self::Item {
+            &mut *(ptr as *mut T)
+        }
+
+        unsafe fn chunk((ptr, stride, len): (*mut c_char, npy_intp, usize)) -> Self::Chunk {
+            chunk_view_mut((ptr as *mut T, stride, len))
+        }
+    }
+
+    /// A tuple of operands that represents the mode of a multi-iterator
+    /// (E.g., `(RO<f64>, RW<bool>)`).
+    pub trait MultiIterMode {
+        private_decl!();
+        /// The operand flags passed to NumPy.
+        fn flags() -> Vec<npy_uint32>;
+        /// The element types of the operands.
+        fn dtypes(py: Python) -> Vec<&PyArrayDescr>;
+        /// The alignments required by the element types of the operands.
+        fn aligns() -> Vec<usize>;
+    }
+
+    /// Appends the operand `O` to a tuple of operands.
+    pub trait PushOperand<O: IterOperand>: MultiIterMode {
+        /// The tuple of operands with `O` appended.
+        type Output: MultiIterMode;
+    }
+
     /// Represents the iterator mode where at least two arrays are iterated.
     pub trait MultiIterModeWithManyArrays: MultiIterMode {}
-    impl MultiIterModeWithManyArrays for RO<RO<()>> {}
-    impl MultiIterModeWithManyArrays for RO<RW<()>> {}
-    impl MultiIterModeWithManyArrays for RW<RO<()>> {}
-    impl MultiIterModeWithManyArrays for RW<RW<()>> {}
 
-    impl<S: MultiIterModeWithManyArrays> MultiIterModeWithManyArrays for RO<S> {}
-    impl<S: MultiIterModeWithManyArrays> MultiIterModeWithManyArrays for RW<S> {}
+    impl MultiIterMode for () {
+        private_impl!();
+        fn flags() -> Vec<npy_uint32> {
+            vec![]
+        }
+        fn dtypes(_py: Python) -> Vec<&PyArrayDescr> {
+            vec![]
+        }
+        fn aligns() -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    impl<O: IterOperand> PushOperand<O> for () {
+        type Output = (O,);
+    }
+
+    macro_rules! impl_multi_iter_mode {
+        ($($op: ident)+) => {
+            impl<$($op: IterOperand,)+> MultiIterMode for ($($op,)+) {
+                private_impl!();
+                fn flags() -> Vec<npy_uint32> {
+                    vec![$($op::FLAG,)+]
+                }
+                fn dtypes(py: Python) -> Vec<&PyArrayDescr> {
+                    vec![$($op::Elem::get_dtype(py),)+]
+                }
+                fn aligns() -> Vec<usize> {
+                    vec![$(std::mem::align_of::<$op::Elem>(),)+]
+                }
+            }
+        };
+    }
+
+    macro_rules! impl_push_operand {
+        ($($op: ident)+; $last: ident) => {
+            impl<$($op: IterOperand,)+ $last: IterOperand> PushOperand<$last> for ($($op,)+) {
+                type Output = ($($op,)+ $last,);
+            }
+        };
+    }
+
+    macro_rules! impl_many_arrays {
+        ($($op: ident)+) => {
+            impl<$($op: IterOperand,)+> MultiIterModeWithManyArrays for ($($op,)+) {}
+        };
+    }
+
+    impl_multi_iter_mode!(A);
+    impl_multi_iter_mode!(A B);
+    impl_multi_iter_mode!(A B C);
+    impl_multi_iter_mode!(A B C D);
+    impl_multi_iter_mode!(A B C D E);
+    impl_multi_iter_mode!(A B C D E F);
+    impl_multi_iter_mode!(A B C D E F G);
+    impl_multi_iter_mode!(A B C D E F G H);
+
+    impl_push_operand!(A; B);
+    impl_push_operand!(A B; C);
+    impl_push_operand!(A B C; D);
+    impl_push_operand!(A B C D; E);
+    impl_push_operand!(A B C D E; F);
+    impl_push_operand!(A B C D E F; G);
+    impl_push_operand!(A B C D E F G; H);
+
+    impl_many_arrays!(A B);
+    impl_many_arrays!(A B C);
+    impl_many_arrays!(A B C D);
+    impl_many_arrays!(A B C D E);
+    impl_many_arrays!(A B C D E F);
+    impl_many_arrays!(A B C D E F G);
+    impl_many_arrays!(A B C D E F G H);
 
     /// Iterator mode for single iterator
-    pub trait IterMode: MultiIterMode {}
+    pub trait IterMode {
+        private_decl!();
+    }
+
     /// Implies Readonly iterator.
-    pub type Readonly = RO<()>;
+    pub struct Readonly;
     /// Implies Readwrite iterator.
-    pub type ReadWrite = RW<()>;
+    pub struct ReadWrite;
 
-    impl IterMode for RO<()> {}
-    impl IterMode for RW<()> {}
+    impl IterMode for Readonly {
+        private_impl!();
+    }
+    impl IterMode for ReadWrite {
+        private_impl!();
+    }
 }
 
 pub use itermode::{
-    IterMode, MultiIterMode, MultiIterModeWithManyArrays, ReadWrite, Readonly, RO, RW,
+    IterMode, IterOperand, MultiIterMode, MultiIterModeWithManyArrays, OperandItem, PushOperand,
+    ReadWrite, Readonly, RO, RW, WO,
 };
 
 /// Builder of [NpySingleIter](./struct.NpySingleIter.html).
 pub struct NpySingleIterBuilder<'py, T, I: IterMode> {
     flags: npy_uint32,
-    array: &'py PyArrayDyn<T>,
-    mode: PhantomData<I>,
+    array: &'py PyAny,
+    mode: PhantomData<(T, I)>,
     was_writable: bool,
+    cast: bool,
+    casting: Casting,
 }
 
 impl<'py, T: Element> NpySingleIterBuilder<'py, T, Readonly> {
@@ -165,9 +327,11 @@ impl<'py, T: Element> NpySingleIterBuilder<'py, T, Readonly> {
         let (array, was_writable) = array.destruct();
         Self {
             flags: NPY_ITER_READONLY,
-            array: array.to_dyn(),
+            array: array.as_ref(),
             mode: PhantomData,
             was_writable,
+            cast: false,
+            casting: Casting::Safe,
         }
     }
 }
@@ -177,9 +341,11 @@ impl<'py, T: Element> NpySingleIterBuilder<'py, T, ReadWrite> {
     pub fn readwrite<D: ndarray::Dimension>(array: &'py PyArray<T, D>) -> Self {
         Self {
             flags: NPY_ITER_READWRITE,
-            array: array.to_dyn(),
+            array: array.as_ref(),
             mode: PhantomData,
             was_writable: false,
+            cast: false,
+            casting: Casting::Safe,
         }
     }
 }
@@ -192,275 +358,1218 @@ impl<'py, T: Element, I: IterMode> NpySingleIterBuilder<'py, T, I> {
         self
     }
 
+    /// Iterates over the elements as type `U` instead of the element type of the array.
+    ///
+    /// The elements are cast using buffering, i.e. [`NpyIterFlag::Buffered`] is implied,
+    /// and for writable iterators, they are cast back when the buffers are flushed.
+    /// Hence the iterator must be created using [`build_lending`](#method.build_lending)
+    /// or [`build_lending_chunked`](#method.build_lending_chunked).
+    /// The kinds of allowed casts are controlled by [`casting`](#method.casting).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numpy::{NpySingleIterBuilder, PyArray};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let array = PyArray::arange(py, 0_i32, 10, 1);
+    ///     let mut iter = NpySingleIterBuilder::readonly(array.readonly().unwrap())
+    ///         .cast::<f64>()
+    ///         .build_lending()
+    ///         .unwrap();
+    ///     let mut sum = 0.0;
+    ///     while let Some(x) = iter.next_item() {
+    ///         sum += x;
+    ///     }
+    ///     assert_eq!(sum, 45.0);
+    /// });
+    /// ```
+    pub fn cast<U: Element>(self) -> NpySingleIterBuilder<'py, U, I> {
+        NpySingleIterBuilder {
+            flags: self.flags,
+            array: self.array,
+            mode: PhantomData,
+            was_writable: self.was_writable,
+            cast: true,
+            casting: self.casting,
+        }
+    }
+
+    /// Sets the kinds of casts allowed by [`cast`](#method.cast), [`Casting::Safe`] by default.
+    #[must_use]
+    pub fn casting(mut self, casting: Casting) -> Self {
+        self.casting = casting;
+        self
+    }
+
     /// Creates an iterator from this builder.
+    ///
+    /// Fails if the iterator is buffered, use [`build_lending`](#method.build_lending) instead.
     pub fn build(self) -> PyResult<NpySingleIter<'py, T, I>> {
-        let array_ptr = self.array.as_array_ptr();
+        self.check_unbuffered()?;
+        Ok(NpySingleIter {
+            raw: self.build_raw(0)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates an iterator from this builder which yields inner loops
+    /// as one-dimensional array views instead of single elements.
+    ///
+    /// Fails if the iterator is buffered,
+    /// use [`build_lending_chunked`](#method.build_lending_chunked) instead.
+    pub fn build_chunked(self) -> PyResult<NpySingleChunkIter<'py, T, I>> {
+        self.check_unbuffered()?;
+        Ok(NpySingleChunkIter {
+            raw: self.build_raw(NPY_ITER_EXTERNAL_LOOP)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates an iterator from this builder which lends each element
+    /// only until the next one is requested, as required for buffered iteration.
+    pub fn build_lending(self) -> PyResult<NpySingleLendingIter<'py, T, I>> {
+        Ok(NpySingleLendingIter {
+            raw: self.build_raw(0)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates an iterator from this builder which lends each inner loop
+    /// only until the next one is requested, as required for buffered iteration.
+    pub fn build_lending_chunked(self) -> PyResult<NpySingleLendingChunkIter<'py, T, I>> {
+        Ok(NpySingleLendingChunkIter {
+            raw: self.build_raw(NPY_ITER_EXTERNAL_LOOP)?,
+            marker: PhantomData,
+        })
+    }
+
+    fn check_unbuffered(&self) -> PyResult<()> {
+        if self.cast || self.flags & NPY_ITER_BUFFERED != 0 {
+            Err(lending_required("buffered"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn build_raw(self, extra_flags: npy_uint32) -> PyResult<RawIter<'py>> {
+        let py = self.array.py();
+        let array_ptr = self.array.as_ptr() as *mut PyArrayObject;
+        let (dtype, extra_flags) = if self.cast {
+            (
+                T::get_dtype(py).as_dtype_ptr(),
+                extra_flags | NPY_ITER_BUFFERED,
+            )
+        } else {
+            (ptr::null_mut(), extra_flags)
+        };
         let iter_ptr = unsafe {
             PY_ARRAY_API.NpyIter_New(
                 array_ptr,
-                self.flags,
+                self.flags | extra_flags,
                 NPY_ORDER::NPY_ANYORDER,
-                NPY_CASTING::NPY_SAFE_CASTING,
-                ptr::null_mut(),
+                self.casting.to_c_enum(),
+                dtype,
             )
         };
-        let py = self.array.py();
-        let readonly_array_ptr = if self.was_writable {
-            Some(array_ptr)
+        let readonly_arrays = if self.was_writable {
+            vec![array_ptr]
         } else {
-            None
+            vec![]
         };
-        NpySingleIter::new(iter_ptr, readonly_array_ptr, py)
+        let raw = RawIter::new(iter_ptr, Arc::new(RestoreWriteable(readonly_arrays)), py)?;
+        raw.check_strides(
+            &[self.flags],
+            &[T::get_dtype(py).itemsize()],
+            &[std::mem::align_of::<T>()],
+            extra_flags,
+        )?;
+        Ok(raw)
     }
 }
 
-/// An iterator over a single array, construced by
-/// [NpySingleIterBuilder](./struct.NpySingleIterBuilder.html).
-/// This iterator iterates all elements in the array as `&mut T` (in case `readwrite` is used)
-/// or `&T` (in case `readonly` is used).
-///
-/// # Example
-///
-/// You can use
-/// [`NpySingleIterBuilder::readwrite`](./struct.NpySingleIterBuilder.html#method.readwrite)
-/// to get a mutable iterator.
-///
-/// ```
-/// use numpy::NpySingleIterBuilder;
-/// pyo3::Python::with_gil(|py| {
-///     let array = numpy::PyArray::arange(py, 0, 10, 1);
-///     let iter = NpySingleIterBuilder::readwrite(array).build().unwrap();
-///     for (i, elem) in iter.enumerate() {
-///         assert_eq!(*elem, i as i64);
-///         *elem = *elem * 2;  // elements are mutable
-///     }
-/// });
-/// ```
-/// Or, as a shorthand, `PyArray::iter` can be also used.
-/// ```
-/// # use numpy::NpySingleIterBuilder;
-/// # pyo3::Python::with_gil(|py| {
-/// #   let array = numpy::PyArray::arange(py, 0, 10, 1);
-/// #   let iter = NpySingleIterBuilder::readwrite(array).build().unwrap();
-///     for (i, elem) in array.iter().unwrap().enumerate() {
-///         assert_eq!(*elem, i as i64);
-///         *elem = *elem * 2;  // elements are mutable
-///     }
-/// });
-/// ```
-/// On the other hand, immutable iterator requires [readonly array](../struct.PyReadonlyArray.html).
-/// ```
-/// use numpy::NpySingleIterBuilder;
-/// pyo3::Python::with_gil(|py| {
-///     let array = numpy::PyArray::arange(py, 0, 1, 10);
-///     let iter = NpySingleIterBuilder::readonly(array.readonly()).build().unwrap();
-///     for (i, elem) in iter.enumerate() {
-///         assert_eq!(*elem, i as i64);
-///     }
-/// });
-/// ```
-pub struct NpySingleIter<'py, T, I> {
+/// Makes the arrays borrowed by an iterator writeable again
+/// once the last iterator using them, including parts created by `split`, is dropped.
+struct RestoreWriteable(Vec<*mut PyArrayObject>);
+
+// The flags are only modified in `drop` which always runs while holding the GIL.
+unsafe impl Send for RestoreWriteable {}
+unsafe impl Sync for RestoreWriteable {}
+
+impl Drop for RestoreWriteable {
+    fn drop(&mut self) {
+        for &array_ptr in &self.0 {
+            unsafe { (*array_ptr).flags |= NPY_ARRAY_WRITEABLE };
+        }
+    }
+}
+
+fn lending_required(kind: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "{} iterators must be created using `build_lending` or `build_lending_chunked`",
+        kind
+    ))
+}
+
+/// The state shared by all iterators: the underlying `NpyIter` and the arrays
+/// which need to be made writeable again after iteration.
+struct RawIter<'py> {
     iterator: ptr::NonNull<NpyIter>,
     iternext: unsafe extern "C" fn(*mut NpyIter) -> c_int,
-    empty: bool,
+    started: bool,
+    finished: bool,
     iter_size: npy_intp,
     dataptr: *mut *mut c_char,
-    return_type: PhantomData<T>,
-    mode: PhantomData<I>,
-    readonly_array_ptr: Option<*mut PyArrayObject>,
-    _py: Python<'py>,
+    // Only available if the external loop is used.
+    inner_strides: *mut npy_intp,
+    inner_size: *mut npy_intp,
+    // Only available if a C or Fortran index is tracked.
+    index: *mut npy_intp,
+    // Only available if a multi-index is tracked.
+    get_multi_index: NpyIter_GetMultiIndexFunc,
+    readonly_arrays: Arc<RestoreWriteable>,
+    py: Python<'py>,
 }
 
-impl<'py, T, I> NpySingleIter<'py, T, I> {
+impl<'py> RawIter<'py> {
     fn new(
         iterator: *mut NpyIter,
-        readonly_array_ptr: Option<*mut PyArrayObject>,
+        readonly_arrays: Arc<RestoreWriteable>,
         py: Python<'py>,
     ) -> PyResult<Self> {
-        let mut iterator = match ptr::NonNull::new(iterator) {
+        let iterator = match ptr::NonNull::new(iterator) {
             Some(iter) => iter,
+            None => return Err(PyErr::fetch(py)),
+        };
+
+        let mut raw = Self {
+            iterator,
+            iternext: noop_iternext,
+            started: false,
+            finished: false,
+            iter_size: 0,
+            dataptr: ptr::null_mut(),
+            inner_strides: ptr::null_mut(),
+            inner_size: ptr::null_mut(),
+            index: ptr::null_mut(),
+            get_multi_index: None,
+            readonly_arrays,
+            py,
+        };
+
+        let iter_ptr = raw.iterator.as_ptr();
+        raw.iternext = match unsafe { PY_ARRAY_API.NpyIter_GetIterNext(iter_ptr, ptr::null_mut()) }
+        {
+            Some(ptr) => ptr,
             None => {
                 return Err(PyErr::fetch(py));
             }
         };
+        raw.dataptr = unsafe { PY_ARRAY_API.NpyIter_GetDataPtrArray(iter_ptr) };
+        if raw.dataptr.is_null() {
+            return Err(PyErr::fetch(py));
+        }
+        if unsafe { PY_ARRAY_API.NpyIter_HasExternalLoop(iter_ptr) } != 0 {
+            raw.inner_strides = unsafe { PY_ARRAY_API.NpyIter_GetInnerStrideArray(iter_ptr) };
+            raw.inner_size = unsafe { PY_ARRAY_API.NpyIter_GetInnerLoopSizePtr(iter_ptr) };
+        }
+        if unsafe { PY_ARRAY_API.NpyIter_HasIndex(iter_ptr) } != 0 {
+            raw.index = unsafe { PY_ARRAY_API.NpyIter_GetIndexPtr(iter_ptr) };
+        }
+        if unsafe { PY_ARRAY_API.NpyIter_HasMultiIndex(iter_ptr) } != 0 {
+            raw.get_multi_index =
+                unsafe { PY_ARRAY_API.NpyIter_GetGetMultiIndex(iter_ptr, ptr::null_mut()) };
+            if raw.get_multi_index.is_none() {
+                return Err(PyErr::fetch(py));
+            }
+        }
+        raw.iter_size = unsafe { PY_ARRAY_API.NpyIter_GetIterSize(iter_ptr) };
+        raw.finished = raw.iter_size == 0;
 
-        let iternext =
-            match unsafe { PY_ARRAY_API.NpyIter_GetIterNext(iterator.as_mut(), ptr::null_mut()) } {
-                Some(ptr) => ptr,
-                None => {
-                    return Err(PyErr::fetch(py));
-                }
-            };
-        let dataptr = unsafe { PY_ARRAY_API.NpyIter_GetDataPtrArray(iterator.as_mut()) };
+        Ok(raw)
+    }
 
-        if dataptr.is_null() {
-            unsafe { PY_ARRAY_API.NpyIter_Deallocate(iterator.as_mut()) };
-            return Err(PyErr::fetch(py));
+    /// Moves to the next element or inner loop, returning `false` when the iteration is complete.
+    ///
+    /// The state of the iterator always reflects the last item returned.
+    fn advance(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        if self.started {
+            self.finished = unsafe { (self.iternext)(self.iterator.as_ptr()) } == 0;
+        } else {
+            self.started = true;
         }
+        !self.finished
+    }
 
-        let iter_size = unsafe { PY_ARRAY_API.NpyIter_GetIterSize(iterator.as_mut()) };
+    /// Returns the data pointer of the `i`-th operand.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be smaller than the number of operands.
+    unsafe fn ptr<T>(&self, i: usize) -> *mut T {
+        *self.dataptr.add(i) as *mut T
+    }
 
-        Ok(Self {
-            iterator,
-            iternext,
-            iter_size,
-            empty: iter_size == 0,
-            dataptr,
-            return_type: PhantomData,
-            mode: PhantomData,
-            readonly_array_ptr,
-            _py: py,
-        })
+    /// Returns the inner loop of the `i`-th operand as its data pointer, stride and length.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be smaller than the number of operands and the external loop must be used.
+    unsafe fn chunk<T>(&self, i: usize) -> (*mut T, npy_intp, usize) {
+        (
+            self.ptr(i),
+            *self.inner_strides.add(i),
+            *self.inner_size as usize,
+        )
     }
 
-    fn iternext(&mut self) -> Option<*mut T> {
-        if self.empty {
+    fn index(&self) -> Option<usize> {
+        if self.index.is_null() {
             None
         } else {
-            // Note: This pointer is correct and doesn't need to be updated,
-            // note that we're derefencing a **char into a *char casting to a *T
-            // and then transforming that into a reference, the value that dataptr
-            // points to is being updated by iternext to point to the next value.
-            let ret = unsafe { *self.dataptr as *mut T };
-            self.empty = unsafe { (self.iternext)(self.iterator.as_mut()) } == 0;
-            Some(ret)
+            Some(unsafe { *self.index } as usize)
         }
     }
-}
 
-impl<'py, T, I> Drop for NpySingleIter<'py, T, I> {
-    fn drop(&mut self) {
-        let _success = unsafe { PY_ARRAY_API.NpyIter_Deallocate(self.iterator.as_mut()) };
-        if let Some(ptr) = self.readonly_array_ptr {
-            unsafe {
-                (*ptr).flags |= NPY_ARRAY_WRITEABLE;
-            }
-        }
+    fn multi_index(&self) -> Option<IxDyn> {
+        let get_multi_index = self.get_multi_index?;
+        let mut index = vec![0; self.ndim()];
+        unsafe { get_multi_index(self.iterator.as_ptr(), index.as_mut_ptr()) };
+        Some(IxDyn(
+            &index.into_iter().map(|i| i as usize).collect::<Vec<_>>(),
+        ))
     }
-}
 
-impl<'py, T: 'py> std::iter::Iterator for NpySingleIter<'py, T, Readonly> {
-    type Item = &'py T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iternext().map(|ptr| unsafe { &*ptr })
+    fn operand_ptrs(&self) -> *mut *mut PyArrayObject {
+        unsafe { PY_ARRAY_API.NpyIter_GetOperandArray(self.iterator.as_ptr()) }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.iter_size as usize, Some(self.iter_size as usize))
+    fn operand<U: Element, D: Dimension>(&self, index: usize) -> PyResult<&'py PyArray<U, D>> {
+        let nop = unsafe { PY_ARRAY_API.NpyIter_GetNOp(self.iterator.as_ptr()) } as usize;
+        if index >= nop {
+            return Err(PyValueError::new_err(format!(
+                "operand index {} is out of bounds for an iterator with {} operands",
+                index, nop
+            )));
+        }
+        let array: &PyAny = unsafe {
+            self.py
+                .from_borrowed_ptr(*self.operand_ptrs().add(index) as *mut pyo3::ffi::PyObject)
+        };
+        array.extract()
     }
-}
 
-impl<'py, T: 'py> std::iter::Iterator for NpySingleIter<'py, T, ReadWrite> {
-    type Item = &'py mut T;
+    fn ndim(&self) -> usize {
+        unsafe { PY_ARRAY_API.NpyIter_GetNDim(self.iterator.as_ptr()) as usize }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iternext().map(|ptr| unsafe { &mut *ptr })
+    fn goto_index(&mut self, index: usize) -> PyResult<()> {
+        if self.index.is_null() {
+            return Err(PyValueError::new_err(
+                "the iterator does not track a C or Fortran index",
+            ));
+        }
+        let ret = unsafe { PY_ARRAY_API.NpyIter_GotoIndex(self.iterator.as_ptr(), index as _) };
+        self.goto_result(ret)
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.iter_size as usize, Some(self.iter_size as usize))
+    fn goto_multi_index(&mut self, index: &[usize]) -> PyResult<()> {
+        if self.get_multi_index.is_none() {
+            return Err(PyValueError::new_err(
+                "the iterator does not track a multi-index",
+            ));
+        }
+        if index.len() != self.ndim() {
+            return Err(PyValueError::new_err(format!(
+                "multi-index of length {} does not match the {} dimensions of the iterator",
+                index.len(),
+                self.ndim()
+            )));
+        }
+        let mut index = index.iter().map(|&i| i as npy_intp).collect::<Vec<_>>();
+        let ret = unsafe {
+            PY_ARRAY_API.NpyIter_GotoMultiIndex(self.iterator.as_ptr(), index.as_mut_ptr())
+        };
+        self.goto_result(ret)
     }
-}
 
-/// Builder for [NpyMultiIter](./struct.NpyMultiIter.html).
-pub struct NpyMultiIterBuilder<'py, T, S: MultiIterMode> {
-    flags: npy_uint32,
-    arrays: Vec<&'py PyArrayDyn<T>>,
-    structure: PhantomData<S>,
-    was_writables: Vec<bool>,
-}
+    /// Positions the iterator such that the next item is the one moved to if successful.
+    fn goto_result(&mut self, ret: c_int) -> PyResult<()> {
+        // The `Goto*` functions return `NPY_SUCCEED`, i.e. one, or `NPY_FAIL`, i.e. zero.
+        if ret == 0 {
+            return Err(PyErr::fetch(self.py));
+        }
+        self.started = false;
+        self.finished = false;
+        Ok(())
+    }
 
-impl<'py, T: Element> Default for NpyMultiIterBuilder<'py, T, ()> {
-    fn default() -> Self {
-        Self::new()
+    fn is_first_visit(&self, operand: usize) -> bool {
+        let nop = unsafe { PY_ARRAY_API.NpyIter_GetNOp(self.iterator.as_ptr()) } as usize;
+        assert!(operand < nop, "operand index out of bounds");
+        unsafe { PY_ARRAY_API.NpyIter_IsFirstVisit(self.iterator.as_ptr(), operand as c_int) != 0 }
     }
-}
 
-impl<'py, T: Element> NpyMultiIterBuilder<'py, T, ()> {
-    /// Creates a new builder.
-    pub fn new() -> Self {
-        Self {
-            flags: 0,
-            arrays: Vec::new(),
-            structure: PhantomData,
-            was_writables: Vec::new(),
+    /// Checks that the elements of writable operands are not repeated and do not overlap
+    /// other operands, which would alias the references returned by the iterator, and for the external loop,
+    /// that all strides are multiples of the item size so that inner loops can be viewed as arrays.
+    fn check_strides(
+        &self,
+        op_flags: &[npy_uint32],
+        itemsizes: &[usize],
+        aligns: &[usize],
+        flags: npy_uint32,
+    ) -> PyResult<()> {
+        for (i, ((&op_flags, &itemsize), &align)) in
+            op_flags.iter().zip(itemsizes).zip(aligns).enumerate()
+        {
+            let array = unsafe { &**self.operand_ptrs().add(i) };
+            // Operands with another item size need to be cast and are hence always buffered.
+            if unsafe { (*array.descr).elsize } as usize != itemsize {
+                continue;
+            }
+            if array.data as usize % align != 0 {
+                return Err(NotAlignedError::new(align).into());
+            }
+            if array.nd == 0 {
+                continue;
+            }
+            let (shape, strides) = unsafe {
+                (
+                    std::slice::from_raw_parts(array.dimensions, array.nd as usize),
+                    std::slice::from_raw_parts(array.strides, array.nd as usize),
+                )
+            };
+            for (&len, &stride) in shape.iter().zip(strides).filter(|(&len, _)| len > 1) {
+                if stride % align as npy_intp != 0 {
+                    return Err(NotAlignedError::new(align).into());
+                }
+                if stride == 0 && op_flags & NPY_ITER_READWRITE != 0 {
+                    return Err(PyValueError::new_err(format!(
+                        "operand {} is writable but repeats its elements along an axis of length {} with stride zero",
+                        i, len
+                    )));
+                }
+                if flags & NPY_ITER_EXTERNAL_LOOP != 0 && stride % itemsize as npy_intp != 0 {
+                    return Err(PyValueError::new_err(format!(
+                        "cannot iterate operand {} in chunks as its stride {} is not a multiple of its item size {}",
+                        i, stride, itemsize
+                    )));
+                }
+            }
+        }
+
+        let bounds = (0..op_flags.len())
+            .map(|i| memory_bounds(unsafe { &**self.operand_ptrs().add(i) }))
+            .collect::<Vec<_>>();
+        for (i, (&op_flags, &bounds_i)) in op_flags.iter().zip(&bounds).enumerate() {
+            let (start, end) = match bounds_i {
+                Some(bounds) if op_flags & NPY_ITER_READWRITE != 0 => bounds,
+                _ => continue,
+            };
+            for (j, &bounds_j) in bounds.iter().enumerate() {
+                match bounds_j {
+                    Some((other_start, other_end))
+                        if i != j && start < other_end && other_start < end =>
+                    {
+                        return Err(PyValueError::new_err(format!(
+                            "operand {} is writable but overlaps operand {} in memory",
+                            i, j
+                        )));
+                    }
+                    _ => {}
+                }
+            }
         }
+        Ok(())
     }
 
-    /// Set a flag to this builder, returning `self`.
-    #[must_use]
-    pub fn set(mut self, flag: NpyIterFlag) -> Self {
-        self.flags |= flag.to_c_enum();
-        self
+    fn needs_api(&self) -> bool {
+        unsafe { PY_ARRAY_API.NpyIter_IterationNeedsAPI(self.iterator.as_ptr()) != 0 }
     }
-}
 
-impl<'py, T: Element, S: MultiIterMode> NpyMultiIterBuilder<'py, T, S> {
-    /// Add a readonly array to the resulting iterator.
-    pub fn add_readonly<D: ndarray::Dimension>(
-        mut self,
-        array: PyReadonlyArray<'py, T, D>,
-    ) -> NpyMultiIterBuilder<'py, T, RO<S>> {
-        let (array, was_writable) = array.destruct();
-        self.arrays.push(array.to_dyn());
-        self.was_writables.push(was_writable);
-        NpyMultiIterBuilder {
-            flags: self.flags,
-            arrays: self.arrays,
-            was_writables: self.was_writables,
-            structure: PhantomData,
+    /// Splits the items which were not yet returned into at most `n` copies of this iterator
+    /// over consecutive ranges of iteration indices.
+    fn split(&self, n: usize) -> PyResult<Vec<RawIter<'py>>> {
+        let iter_ptr = self.iterator.as_ptr();
+        if self.finished {
+            return Ok(Vec::new());
         }
-    }
+        let (mut start, mut end) = (0, 0);
+        unsafe { PY_ARRAY_API.NpyIter_GetIterIndexRange(iter_ptr, &mut start, &mut end) };
+        let start =
+            unsafe { PY_ARRAY_API.NpyIter_GetIterIndex(iter_ptr) } + self.started as npy_intp;
+        let remaining = end - start;
+        if remaining <= 0 {
+            return Ok(Vec::new());
+        }
+        let n = (n.max(1) as npy_intp).min(remaining);
 
-    /// Adds a writable array to the resulting iterator.
-    pub fn add_readwrite<D: ndarray::Dimension>(
-        mut self,
-        array: &'py PyArray<T, D>,
-    ) -> NpyMultiIterBuilder<'py, T, RW<S>> {
-        self.arrays.push(array.to_dyn());
-        self.was_writables.push(false);
-        NpyMultiIterBuilder {
-            flags: self.flags,
-            arrays: self.arrays,
-            was_writables: self.was_writables,
-            structure: PhantomData,
+        let mut parts = Vec::with_capacity(n as usize);
+        let mut part_start = start;
+        for i in 0..n {
+            let part_end = part_start + remaining / n + (i < remaining % n) as npy_intp;
+            let copy = unsafe { PY_ARRAY_API.NpyIter_Copy(iter_ptr) };
+            let mut part = RawIter::new(copy, self.readonly_arrays.clone(), self.py)?;
+            let ret = unsafe {
+                PY_ARRAY_API.NpyIter_ResetToIterIndexRange(
+                    part.iterator.as_ptr(),
+                    part_start,
+                    part_end,
+                    ptr::null_mut(),
+                )
+            };
+            if ret == 0 {
+                return Err(PyErr::fetch(self.py));
+            }
+            part.iter_size = part_end - part_start;
+            part.finished = false;
+            parts.push(part);
+            part_start = part_end;
         }
+        Ok(parts)
     }
 }
 
-impl<'py, T: Element, S: MultiIterModeWithManyArrays> NpyMultiIterBuilder<'py, T, S> {
+impl<'py> Drop for RawIter<'py> {
+    fn drop(&mut self) {
+        let _success = unsafe { PY_ARRAY_API.NpyIter_Deallocate(self.iterator.as_ptr()) };
+    }
+}
+
+unsafe extern "C" fn noop_iternext(_iter: *mut NpyIter) -> c_int {
+    0
+}
+
+/// Creates a view of an inner loop with the given byte stride.
+///
+/// # Safety
+///
+/// The inner loop must be valid for reads of `T` for the lifetime `'a`.
+unsafe fn chunk_view<'a, T>((ptr, stride, len): (*mut T, npy_intp, usize)) -> ArrayView1<'a, T> {
+    let (ptr, stride, inverted) = normalize_chunk(ptr, stride, len);
+    let mut view = ArrayView1::from_shape_ptr(Ix1(len).strides(Ix1(stride)), ptr);
+    if inverted {
+        view.invert_axis(Axis(0));
+    }
+    view
+}
+
+/// Returns the range of bytes spanned by the elements of `array`, or `None` if it is empty.
+fn memory_bounds(array: &PyArrayObject) -> Option<(usize, usize)> {
+    let (shape, strides) = unsafe {
+        (
+            std::slice::from_raw_parts(array.dimensions, array.nd as usize),
+            std::slice::from_raw_parts(array.strides, array.nd as usize),
+        )
+    };
+    if shape.contains(&0) {
+        return None;
+    }
+    let (mut start, mut end) = (array.data as isize, array.data as isize);
+    for (&len, &stride) in shape.iter().zip(strides) {
+        let extent = (len - 1) * stride;
+        if extent < 0 {
+            start += extent;
+        } else {
+            end += extent;
+        }
+    }
+    let itemsize = unsafe { (*array.descr).elsize } as isize;
+    Some((start as usize, (end + itemsize) as usize))
+}
+
+/// Creates a mutable view of an inner loop with the given byte stride.
+///
+/// # Safety
+///
+/// The inner loop must be valid for reads and writes of `T` for the lifetime `'a`
+/// and must not be aliased, in particular, its stride must not be zero, c.f. `check_strides`.
+unsafe fn chunk_view_mut<'a, T>(
+    (ptr, stride, len): (*mut T, npy_intp, usize),
+) -> ArrayViewMut1<'a, T> {
+    debug_assert!(stride != 0 || len <= 1, "mutable inner loop is aliased");
+    let (ptr, stride, inverted) = normalize_chunk(ptr, stride, len);
+    let mut view = ArrayViewMut1::from_shape_ptr(Ix1(len).strides(Ix1(stride)), ptr);
+    if inverted {
+        view.invert_axis(Axis(0));
+    }
+    view
+}
+
+/// Converts a byte stride into a non-negative element stride
+/// and moves the pointer to the last element if the stride was negative.
+///
+/// The stride must be a multiple of the item size, c.f. `check_strides`.
+unsafe fn normalize_chunk<T>(ptr: *mut T, stride: npy_intp, len: usize) -> (*mut T, usize, bool) {
+    let itemsize = std::mem::size_of::<T>() as npy_intp;
+    debug_assert_eq!(
+        stride % itemsize,
+        0,
+        "inner loop stride is not a multiple of the item size"
+    );
+    let stride = stride / itemsize;
+    if stride < 0 && len > 0 {
+        let last = ptr.offset(stride * (len as npy_intp - 1));
+        (last, -stride as usize, true)
+    } else {
+        (ptr, stride as usize, false)
+    }
+}
+
+/// An iterator over a single array, construced by
+/// [NpySingleIterBuilder](./struct.NpySingleIterBuilder.html).
+/// This iterator iterates all elements in the array as `&mut T` (in case `readwrite` is used)
+/// or `&T` (in case `readonly` is used).
+///
+/// # Example
+///
+/// You can use
+/// [`NpySingleIterBuilder::readwrite`](./struct.NpySingleIterBuilder.html#method.readwrite)
+/// to get a mutable iterator.
+///
+/// ```
+/// use numpy::NpySingleIterBuilder;
+/// pyo3::Python::with_gil(|py| {
+///     let array = numpy::PyArray::arange(py, 0, 10, 1);
+///     let iter = NpySingleIterBuilder::readwrite(array).build().unwrap();
+///     for (i, elem) in iter.enumerate() {
+///         assert_eq!(*elem, i as i64);
+///         *elem = *elem * 2;  // elements are mutable
+///     }
+/// });
+/// ```
+/// Or, as a shorthand, `PyArray::iter` can be also used.
+/// ```
+/// # use numpy::NpySingleIterBuilder;
+/// # pyo3::Python::with_gil(|py| {
+/// #   let array = numpy::PyArray::arange(py, 0, 10, 1);
+/// #   let iter = NpySingleIterBuilder::readwrite(array).build().unwrap();
+///     for (i, elem) in array.iter().unwrap().enumerate() {
+///         assert_eq!(*elem, i as i64);
+///         *elem = *elem * 2;  // elements are mutable
+///     }
+/// });
+/// ```
+/// On the other hand, immutable iterator requires [readonly array](../struct.PyReadonlyArray.html).
+/// ```
+/// use numpy::NpySingleIterBuilder;
+/// pyo3::Python::with_gil(|py| {
+///     let array = numpy::PyArray::arange(py, 0, 1, 10);
+///     let iter = NpySingleIterBuilder::readonly(array.readonly().unwrap()).build().unwrap();
+///     for (i, elem) in iter.enumerate() {
+///         assert_eq!(*elem, i as i64);
+///     }
+/// });
+/// ```
+pub struct NpySingleIter<'py, T, I> {
+    raw: RawIter<'py>,
+    marker: PhantomData<(T, I)>,
+}
+
+impl<'py, T: 'py> std::iter::Iterator for NpySingleIter<'py, T, Readonly> {
+    type Item = &'py T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.raw.advance() {
+            Some(unsafe { &*self.raw.ptr::<T>(0) })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            self.raw.iter_size as usize,
+            Some(self.raw.iter_size as usize),
+        )
+    }
+}
+
+impl<'py, T: 'py> std::iter::Iterator for NpySingleIter<'py, T, ReadWrite> {
+    type Item = &'py mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.raw.advance() {
+            Some(unsafe { &mut *self.raw.ptr::<T>(0) })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            self.raw.iter_size as usize,
+            Some(self.raw.iter_size as usize),
+        )
+    }
+}
+
+/// An iterator over the inner loops of a single array, constructed by
+/// [`NpySingleIterBuilder::build_chunked`](./struct.NpySingleIterBuilder.html#method.build_chunked).
+///
+/// Each item is a one-dimensional view of as many elements as NumPy can process in one go,
+/// which is much faster than iterating element by element.
+/// The views are `ArrayView1<T>` in case `readonly` is used
+/// or `ArrayViewMut1<T>` in case `readwrite` is used.
+///
+/// # Example
+///
+/// ```
+/// use numpy::NpySingleIterBuilder;
+/// pyo3::Python::with_gil(|py| {
+///     let array = numpy::PyArray::arange(py, 0, 10, 1).reshape([2, 5]).unwrap();
+///     let iter = NpySingleIterBuilder::readwrite(array).build_chunked().unwrap();
+///     for mut chunk in iter {
+///         chunk *= 2;
+///     }
+///     let iter = NpySingleIterBuilder::readonly(array.readonly().unwrap()).build_chunked().unwrap();
+///     assert_eq!(iter.map(|chunk| chunk.sum()).sum::<i64>(), 90);
+/// });
+/// ```
+pub struct NpySingleChunkIter<'py, T, I> {
+    raw: RawIter<'py>,
+    marker: PhantomData<(T, I)>,
+}
+
+impl<'py, T: 'py> std::iter::Iterator for NpySingleChunkIter<'py, T, Readonly> {
+    type Item = ArrayView1<'py, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.raw.advance() {
+            Some(unsafe { chunk_view(self.raw.chunk(0)) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'py, T: 'py> std::iter::Iterator for NpySingleChunkIter<'py, T, ReadWrite> {
+    type Item = ArrayViewMut1<'py, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.raw.advance() {
+            Some(unsafe { chunk_view_mut(self.raw.chunk(0)) })
+        } else {
+            None
+        }
+    }
+}
+
+/// An iterator over a single array which lends each element only until the next one
+/// is requested, constructed by
+/// [`NpySingleIterBuilder::build_lending`](./struct.NpySingleIterBuilder.html#method.build_lending).
+///
+/// This is required for buffered iteration, e.g. when [casting](./struct.NpySingleIterBuilder.html#method.cast),
+/// as the elements are then stored in buffers which are overwritten when the iterator advances.
+/// Elements are `&T` in case `readonly` is used or `&mut T` in case `readwrite` is used.
+///
+/// # Example
+///
+/// ```
+/// use numpy::{NpySingleIterBuilder, PyArray};
+/// pyo3::Python::with_gil(|py| {
+///     let array = PyArray::arange(py, 0_i32, 4, 1);
+///     let mut iter = NpySingleIterBuilder::readwrite(array)
+///         .cast::<f64>()
+///         .casting(numpy::Casting::Unsafe)
+///         .build_lending()
+///         .unwrap();
+///     while let Some(x) = iter.next_item() {
+///         *x *= 2.5;
+///     }
+///     drop(iter);
+///     assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[0, 2, 5, 7]);
+/// });
+/// ```
+pub struct NpySingleLendingIter<'py, T, I> {
+    raw: RawIter<'py>,
+    marker: PhantomData<(T, I)>,
+}
+
+impl<'py, T> NpySingleLendingIter<'py, T, Readonly> {
+    /// Returns the next element which is borrowed until the iterator is used again.
+    pub fn next_item(&mut self) -> Option<&T> {
+        if self.raw.advance() {
+            Some(unsafe { &*self.raw.ptr::<T>(0) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'py, T> NpySingleLendingIter<'py, T, ReadWrite> {
+    /// Returns the next element which is borrowed until the iterator is used again.
+    pub fn next_item(&mut self) -> Option<&mut T> {
+        if self.raw.advance() {
+            Some(unsafe { &mut *self.raw.ptr::<T>(0) })
+        } else {
+            None
+        }
+    }
+}
+
+/// An iterator over the inner loops of a single array which lends each of them only until
+/// the next one is requested, constructed by
+/// [`NpySingleIterBuilder::build_lending_chunked`](./struct.NpySingleIterBuilder.html#method.build_lending_chunked).
+///
+/// See [NpySingleLendingIter](./struct.NpySingleLendingIter.html) and
+/// [NpySingleChunkIter](./struct.NpySingleChunkIter.html) for details.
+///
+/// # Example
+///
+/// ```
+/// use numpy::{NpySingleIterBuilder, PyArray};
+/// pyo3::Python::with_gil(|py| {
+///     let array = PyArray::arange(py, 0_u8, 10, 1);
+///     let mut iter = NpySingleIterBuilder::readonly(array.readonly().unwrap())
+///         .cast::<u64>()
+///         .build_lending_chunked()
+///         .unwrap();
+///     let mut sum = 0;
+///     while let Some(chunk) = iter.next_chunk() {
+///         sum += chunk.sum();
+///     }
+///     assert_eq!(sum, 45);
+/// });
+/// ```
+pub struct NpySingleLendingChunkIter<'py, T, I> {
+    raw: RawIter<'py>,
+    marker: PhantomData<(T, I)>,
+}
+
+impl<'py, T> NpySingleLendingChunkIter<'py, T, Readonly> {
+    /// Returns the next inner loop which is borrowed until the iterator is used again.
+    pub fn next_chunk(&mut self) -> Option<ArrayView1<'_, T>> {
+        if self.raw.advance() {
+            Some(unsafe { chunk_view(self.raw.chunk(0)) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'py, T> NpySingleLendingChunkIter<'py, T, ReadWrite> {
+    /// Returns the next inner loop which is borrowed until the iterator is used again.
+    pub fn next_chunk(&mut self) -> Option<ArrayViewMut1<'_, T>> {
+        if self.raw.advance() {
+            Some(unsafe { chunk_view_mut(self.raw.chunk(0)) })
+        } else {
+            None
+        }
+    }
+}
+
+/// Builder for [NpyMultiIter](./struct.NpyMultiIter.html).
+///
+/// Each operand can have its own element type which is tracked by the type parameter `S`,
+/// a tuple of [RO](./struct.RO.html), [RW](./struct.RW.html) and [WO](./struct.WO.html)
+/// in the order the operands were added. Up to eight operands are supported.
+pub struct NpyMultiIterBuilder<'py, S: MultiIterMode> {
+    flags: npy_uint32,
+    operands: Vec<Operand<'py>>,
+    casting: Casting,
+    op_axes: Vec<(usize, Vec<c_int>)>,
+    structure: PhantomData<S>,
+}
+
+struct Operand<'py> {
+    // `None` for outputs allocated by the iterator.
+    array: Option<&'py PyAny>,
+    was_writable: bool,
+    cast: bool,
+}
+
+impl<'py> Default for NpyMultiIterBuilder<'py, ()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'py> NpyMultiIterBuilder<'py, ()> {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self {
+            flags: 0,
+            operands: Vec::new(),
+            casting: Casting::Safe,
+            op_axes: Vec::new(),
+            structure: PhantomData,
+        }
+    }
+
+    /// Set a flag to this builder, returning `self`.
+    #[must_use]
+    pub fn set(mut self, flag: NpyIterFlag) -> Self {
+        self.flags |= flag.to_c_enum();
+        self
+    }
+}
+
+impl<'py, S: MultiIterMode> NpyMultiIterBuilder<'py, S> {
+    /// Add a readonly array to the resulting iterator.
+    pub fn add_readonly<T: Element, D: ndarray::Dimension>(
+        self,
+        array: PyReadonlyArray<'py, T, D>,
+    ) -> NpyMultiIterBuilder<'py, S::Output>
+    where
+        S: PushOperand<RO<T>>,
+    {
+        let (array, was_writable) = array.destruct();
+        self.push(Some(array.as_ref()), was_writable, false)
+    }
+
+    /// Adds a writable array to the resulting iterator.
+    pub fn add_readwrite<T: Element, D: ndarray::Dimension>(
+        self,
+        array: &'py PyArray<T, D>,
+    ) -> NpyMultiIterBuilder<'py, S::Output>
+    where
+        S: PushOperand<RW<T>>,
+    {
+        self.push(Some(array.as_ref()), false, false)
+    }
+
+    /// Adds a readonly array to the resulting iterator whose elements are cast to `U`.
+    ///
+    /// The elements are cast using buffering, i.e. [`NpyIterFlag::Buffered`] is implied,
+    /// so that the iterator must be created using [`build_lending`](#method.build_lending)
+    /// or [`build_lending_chunked`](#method.build_lending_chunked).
+    /// The kinds of allowed casts are controlled by [`casting`](#method.casting).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numpy::{NpyMultiIterBuilder, PyArray};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let x = PyArray::arange(py, 0_i32, 4, 1);
+    ///     let y = PyArray::arange(py, 0.5_f64, 4.0, 1.0);
+    ///     let mut iter = NpyMultiIterBuilder::new()
+    ///         .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+    ///         .add_readwrite(y)
+    ///         .build_lending()
+    ///         .unwrap();
+    ///     while let Some((x, y)) = iter.next_item() {
+    ///         *y += x;
+    ///     }
+    ///     drop(iter);
+    ///     assert_eq!(y.readonly().unwrap().as_slice().unwrap(), &[0.5, 2.5, 4.5, 6.5]);
+    /// });
+    /// ```
+    pub fn add_readonly_as<U: Element, T: Element, D: ndarray::Dimension>(
+        self,
+        array: PyReadonlyArray<'py, T, D>,
+    ) -> NpyMultiIterBuilder<'py, S::Output>
+    where
+        S: PushOperand<RO<U>>,
+    {
+        let (array, was_writable) = array.destruct();
+        self.push(Some(array.as_ref()), was_writable, true)
+    }
+
+    /// Adds a writable array to the resulting iterator whose elements are cast to `U`
+    /// and cast back when the buffers are flushed, i.e. after the iteration is complete.
+    ///
+    /// See [`add_readonly_as`](#method.add_readonly_as) for details.
+    pub fn add_readwrite_as<U: Element, T: Element, D: ndarray::Dimension>(
+        self,
+        array: &'py PyArray<T, D>,
+    ) -> NpyMultiIterBuilder<'py, S::Output>
+    where
+        S: PushOperand<RW<U>>,
+    {
+        self.push(Some(array.as_ref()), false, true)
+    }
+
+    /// Adds an output array with elements of type `T` which is allocated by the iterator.
+    ///
+    /// Its shape is the broadcast shape of the other operands unless its axes are mapped using
+    /// [`op_axes`](#method.op_axes) and its elements are initialized to zero.
+    /// It can be retrieved using the `operand` method of the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numpy::{NpyMultiIterBuilder, PyArray, PyArray2};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let x = PyArray::arange(py, 0.0, 3.0, 1.0);
+    ///     let y = PyArray::arange(py, 0.0, 2.0, 1.0).reshape([2, 1]).unwrap();
+    ///     let iter = NpyMultiIterBuilder::new()
+    ///         .add_readonly(x.readonly().unwrap())
+    ///         .add_readonly(y.readonly().unwrap())
+    ///         .add_output::<f64>()
+    ///         .build()
+    ///         .unwrap();
+    ///     let z: &PyArray2<f64> = iter.operand(2).unwrap();
+    ///     for (x, y, z) in iter {
+    ///         *z = x + 10.0 * y;
+    ///     }
+    ///     assert_eq!(z.shape(), [2, 3]);
+    ///     assert_eq!(z.readonly().unwrap().as_slice().unwrap(), &[0.0, 1.0, 2.0, 10.0, 11.0, 12.0]);
+    /// });
+    /// ```
+    pub fn add_output<T: Element>(self) -> NpyMultiIterBuilder<'py, S::Output>
+    where
+        S: PushOperand<WO<T>>,
+    {
+        self.push(None, false, false)
+    }
+
+    /// Sets the kinds of casts allowed for operands added using
+    /// [`add_readonly_as`](#method.add_readonly_as) or [`add_readwrite_as`](#method.add_readwrite_as),
+    /// [`Casting::Safe`] by default.
+    #[must_use]
+    pub fn casting(mut self, casting: Casting) -> Self {
+        self.casting = casting;
+        self
+    }
+
+    /// Maps the axes of the iteration to the axes of the `operand`-th operand.
+    ///
+    /// `axes[i]` is the axis of the operand corresponding to the `i`-th axis of the iteration
+    /// or `None` if the operand is broadcast along that axis. If [`NpyIterFlag::ReduceOk`] is set,
+    /// outputs are reduced along the axes marked `None` instead, so that each of their elements
+    /// is visited multiple times, c.f. [`NpyMultiLendingIter::is_first_visit`].
+    /// Reducing iterators must be created using [`build_lending`](#method.build_lending).
+    ///
+    /// All operands with mapped axes must use the same number of iteration axes.
+    /// Operands without mapped axes are broadcast as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numpy::{NpyIterFlag, NpyMultiIterBuilder, PyArray, PyArray2};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let x = PyArray::arange(py, 0, 24, 1).reshape([2, 3, 4]).unwrap();
+    ///     let mut iter = NpyMultiIterBuilder::new()
+    ///         .set(NpyIterFlag::ReduceOk)
+    ///         .add_readonly(x.readonly().unwrap())
+    ///         .add_output::<i64>()
+    ///         .op_axes(1, &[Some(0), Some(1), None])
+    ///         .build_lending()
+    ///         .unwrap();
+    ///     let sums: &PyArray2<i64> = iter.operand(1).unwrap();
+    ///     while let Some((x, sum)) = iter.next_item() {
+    ///         *sum += x;
+    ///     }
+    ///     assert_eq!(sums.readonly().unwrap().as_slice().unwrap(), &[6, 22, 38, 54, 70, 86]);
+    /// });
+    /// ```
+    #[must_use]
+    pub fn op_axes(mut self, operand: usize, axes: &[Option<usize>]) -> Self {
+        let axes = axes
+            .iter()
+            .map(|axis| axis.map_or(-1, |axis| axis as c_int))
+            .collect();
+        self.op_axes.retain(|(op, _)| *op != operand);
+        self.op_axes.push((operand, axes));
+        self
+    }
+
+    fn push<O: IterOperand, S2: MultiIterMode>(
+        mut self,
+        array: Option<&'py PyAny>,
+        was_writable: bool,
+        cast: bool,
+    ) -> NpyMultiIterBuilder<'py, S2>
+    where
+        S: PushOperand<O, Output = S2>,
+    {
+        self.operands.push(Operand {
+            array,
+            was_writable,
+            cast,
+        });
+        NpyMultiIterBuilder {
+            flags: self.flags,
+            operands: self.operands,
+            casting: self.casting,
+            op_axes: self.op_axes,
+            structure: PhantomData,
+        }
+    }
+}
+
+impl<'py, S: MultiIterModeWithManyArrays> NpyMultiIterBuilder<'py, S> {
     /// Creates an iterator from this builder.
-    pub fn build(self) -> PyResult<NpyMultiIter<'py, T, S>> {
+    ///
+    /// Fails if the iterator is buffered or [`NpyIterFlag::ReduceOk`] is set,
+    /// use [`build_lending`](#method.build_lending) instead.
+    pub fn build(self) -> PyResult<NpyMultiIter<'py, S>> {
+        self.check_unbuffered()?;
+        Ok(NpyMultiIter {
+            raw: self.build_raw(0)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates an iterator from this builder which yields inner loops
+    /// as tuples of one-dimensional array views instead of single elements.
+    ///
+    /// Fails if the iterator is buffered, use [`build_lending_chunked`](#method.build_lending_chunked)
+    /// instead, or if [`NpyIterFlag::ReduceOk`] is set as reduced operands cannot be viewed as arrays.
+    pub fn build_chunked(self) -> PyResult<NpyMultiChunkIter<'py, S>> {
+        self.check_unbuffered()?;
+        Ok(NpyMultiChunkIter {
+            raw: self.build_raw(NPY_ITER_EXTERNAL_LOOP)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates an iterator from this builder which lends each item
+    /// only until the next one is requested, as required for buffered iteration and reductions.
+    pub fn build_lending(self) -> PyResult<NpyMultiLendingIter<'py, S>> {
+        Ok(NpyMultiLendingIter {
+            raw: self.build_raw(0)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates an iterator from this builder which lends each tuple of inner loops
+    /// only until the next one is requested, as required for buffered iteration.
+    pub fn build_lending_chunked(self) -> PyResult<NpyMultiLendingChunkIter<'py, S>> {
+        Ok(NpyMultiLendingChunkIter {
+            raw: self.build_raw(NPY_ITER_EXTERNAL_LOOP)?,
+            marker: PhantomData,
+        })
+    }
+
+    fn check_unbuffered(&self) -> PyResult<()> {
+        if self.flags & NPY_ITER_BUFFERED != 0 || self.operands.iter().any(|op| op.cast) {
+            Err(lending_required("buffered"))
+        } else if self.flags & NPY_ITER_REDUCE_OK != 0 {
+            // Reduced elements are visited repeatedly which would alias mutable references.
+            Err(lending_required("reducing"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn build_raw(self, mut extra_flags: npy_uint32) -> PyResult<RawIter<'py>> {
         let Self {
             flags,
-            arrays,
-            was_writables,
+            operands,
+            casting,
+            op_axes,
             ..
         } = self;
-        debug_assert!(arrays.len() <= std::i32::MAX as usize);
-        debug_assert!(2 <= arrays.len());
+        debug_assert!(operands.len() <= std::i32::MAX as usize);
+        debug_assert!(2 <= operands.len());
 
         let mut opflags = S::flags();
-        let py = arrays[0].py();
-        let mut arrays = arrays
+        let py = match operands.iter().flat_map(|op| op.array).next() {
+            Some(array) => array.py(),
+            None => {
+                return Err(PyValueError::new_err(
+                    "at least one operand must not be allocated by the iterator",
+                ))
+            }
+        };
+        if operands.iter().any(|op| op.cast) {
+            extra_flags |= NPY_ITER_BUFFERED;
+        }
+        if extra_flags & NPY_ITER_EXTERNAL_LOOP != 0 && flags & NPY_ITER_REDUCE_OK != 0 {
+            return Err(PyValueError::new_err(
+                "cannot iterate reductions in chunks as the views of reduced operands would alias",
+            ));
+        }
+        let mut dtypes = operands
             .iter()
-            .map(|x| x.as_array_ptr())
-            .collect::<Vec<_>>()
-            .into_boxed_slice();
+            .zip(S::dtypes(py))
+            .map(|(op, dtype)| {
+                if op.array.is_none() || op.cast {
+                    dtype.as_dtype_ptr()
+                } else {
+                    ptr::null_mut()
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut array_ptrs = operands
+            .iter()
+            .map(|op| {
+                op.array
+                    .map_or(ptr::null_mut(), |x| x.as_ptr() as *mut PyArrayObject)
+            })
+            .collect::<Vec<_>>();
 
-        let iter_ptr = unsafe {
-            PY_ARRAY_API.NpyIter_MultiNew(
-                arrays.len() as i32,
-                arrays.as_mut_ptr(),
-                flags,
-                NPY_ORDER::NPY_ANYORDER,
-                NPY_CASTING::NPY_SAFE_CASTING,
-                opflags.as_mut_ptr(),
-                ptr::null_mut(),
-            )
+        // Created first to restore the flags on early returns.
+        let readonly_arrays = Arc::new(RestoreWriteable(
+            array_ptrs
+                .iter()
+                .zip(&operands)
+                .filter(|(_, op)| op.was_writable)
+                .map(|(&array_ptr, _)| array_ptr)
+                .collect(),
+        ));
+
+        let iter_ptr = if op_axes.is_empty() {
+            unsafe {
+                PY_ARRAY_API.NpyIter_MultiNew(
+                    array_ptrs.len() as i32,
+                    array_ptrs.as_mut_ptr(),
+                    flags | extra_flags,
+                    NPY_ORDER::NPY_ANYORDER,
+                    casting.to_c_enum(),
+                    opflags.as_mut_ptr(),
+                    dtypes.as_mut_ptr(),
+                )
+            }
+        } else {
+            let oa_ndim = op_axes[0].1.len();
+            let mut op_axes_ptrs = vec![ptr::null_mut(); operands.len()];
+            for (operand, axes) in &op_axes {
+                if *operand >= operands.len() {
+                    return Err(PyValueError::new_err(format!(
+                        "cannot map the axes of operand {} as there are only {} operands",
+                        operand,
+                        operands.len()
+                    )));
+                }
+                if axes.len() != oa_ndim {
+                    return Err(PyValueError::new_err(
+                        "the axes of all operands must be mapped to the same number of iteration axes",
+                    ));
+                }
+                op_axes_ptrs[*operand] = axes.as_ptr() as *mut c_int;
+            }
+            unsafe {
+                PY_ARRAY_API.NpyIter_AdvancedNew(
+                    array_ptrs.len() as i32,
+                    array_ptrs.as_mut_ptr(),
+                    flags | extra_flags,
+                    NPY_ORDER::NPY_ANYORDER,
+                    casting.to_c_enum(),
+                    opflags.as_mut_ptr(),
+                    dtypes.as_mut_ptr(),
+                    oa_ndim as c_int,
+                    op_axes_ptrs.as_mut_ptr(),
+                    ptr::null_mut(),
+                    0,
+                )
+            }
         };
-        NpyMultiIter::new(iter_ptr, arrays, was_writables, py)
+        let raw = RawIter::new(iter_ptr, readonly_arrays, py)?;
+        let itemsizes = S::dtypes(py)
+            .iter()
+            .map(|dtype| dtype.itemsize())
+            .collect::<Vec<_>>();
+        raw.check_strides(&opflags, &itemsizes, &S::aligns(), extra_flags)?;
+
+        // Allocated outputs are uninitialized which is not valid for all element types.
+        let zero = 0.to_object(py);
+        for (i, _) in operands
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| op.array.is_none())
+        {
+            let output = unsafe { *raw.operand_ptrs().add(i) };
+            if unsafe { PY_ARRAY_API.PyArray_FillWithScalar(output, zero.as_ptr()) } == -1 {
+                return Err(PyErr::fetch(py));
+            }
+        }
+        Ok(raw)
     }
 }
 
@@ -481,134 +1590,541 @@ impl<'py, T: Element, S: MultiIterModeWithManyArrays> NpyMultiIterBuilder<'py, T
 ///     let array2 = numpy::PyArray::arange(py, 10, 20, 1);
 ///     let array3 = numpy::PyArray::arange(py, 10, 30, 2);
 ///     let iter = NpyMultiIterBuilder::new()
-///         .add_readonly(array1.readonly())
+///         .add_readonly(array1.readonly().unwrap())
 ///         .add_readwrite(array2)
-///         .add_readonly(array3.readonly())
+///         .add_readonly(array3.readonly().unwrap())
 ///         .build()
-///          .unwrap();
+///         .unwrap();
 ///     for (i, j, k) in iter {
 ///         assert_eq!(*i + *j, *k);
 ///         *j += *i + *k;  // The third element is only mutable.
 ///     }
 /// });
 /// ```
-pub struct NpyMultiIter<'py, T, S: MultiIterModeWithManyArrays> {
-    iterator: ptr::NonNull<NpyIter>,
-    iternext: unsafe extern "C" fn(*mut NpyIter) -> c_int,
-    empty: bool,
-    iter_size: npy_intp,
-    dataptr: *mut *mut c_char,
-    marker: PhantomData<(T, S)>,
-    arrays: Box<[*mut PyArrayObject]>,
-    was_writables: Vec<bool>,
-    _py: Python<'py>,
+///
+/// The arrays can have different element types.
+///
+/// ```
+/// use numpy::{NpyMultiIterBuilder, PyArray1};
+/// pyo3::Python::with_gil(|py| {
+///     let values = PyArray1::from_slice(py, &[1.0, 2.0, 3.0]);
+///     let mask = PyArray1::from_slice(py, &[true, false, true]);
+///     let counts = PyArray1::<i64>::zeros(py, 3, false);
+///     let iter = NpyMultiIterBuilder::new()
+///         .add_readonly(values.readonly().unwrap())
+///         .add_readonly(mask.readonly().unwrap())
+///         .add_readwrite(counts)
+///         .build()
+///         .unwrap();
+///     for (value, mask, count) in iter {
+///         if *mask {
+///             *count = *value as i64;
+///         }
+///     }
+///     assert_eq!(counts.readonly().unwrap().as_slice().unwrap(), &[1, 0, 3]);
+/// });
+/// ```
+pub struct NpyMultiIter<'py, S: MultiIterModeWithManyArrays> {
+    raw: RawIter<'py>,
+    marker: PhantomData<S>,
 }
 
-impl<'py, T, S: MultiIterModeWithManyArrays> NpyMultiIter<'py, T, S> {
-    fn new(
-        iterator: *mut NpyIter,
-        arrays: Box<[*mut PyArrayObject]>,
-        was_writables: Vec<bool>,
-        py: Python<'py>,
-    ) -> PyResult<Self> {
-        let mut iterator = match ptr::NonNull::new(iterator) {
-            Some(ptr) => ptr,
-            None => {
-                return Err(PyErr::fetch(py));
+/// An iterator over the inner loops of multiple arrays, constructed by
+/// [`NpyMultiIterBuilder::build_chunked`](./struct.NpyMultiIterBuilder.html#method.build_chunked).
+///
+/// Each item is a tuple of one-dimensional views, one per array, which all have the same length.
+/// Readonly arrays are viewed as `ArrayView1<T>` and writable ones as `ArrayViewMut1<T>`.
+///
+/// # Example
+///
+/// ```
+/// use numpy::NpyMultiIterBuilder;
+/// pyo3::Python::with_gil(|py| {
+///     let array1 = numpy::PyArray::arange(py, 0, 10, 1);
+///     let array2 = numpy::PyArray::arange(py, 10, 20, 1);
+///     let iter = NpyMultiIterBuilder::new()
+///         .add_readonly(array1.readonly().unwrap())
+///         .add_readwrite(array2)
+///         .build_chunked()
+///         .unwrap();
+///     for (x, mut y) in iter {
+///         y += &x;
+///     }
+///     assert_eq!(array2.readonly().unwrap().as_array().sum(), 190);
+/// });
+/// ```
+pub struct NpyMultiChunkIter<'py, S: MultiIterModeWithManyArrays> {
+    raw: RawIter<'py>,
+    marker: PhantomData<S>,
+}
+
+/// An iterator over multiple arrays which lends each item only until the next one
+/// is requested, constructed by
+/// [`NpyMultiIterBuilder::build_lending`](./struct.NpyMultiIterBuilder.html#method.build_lending).
+///
+/// This is required for buffered iteration, e.g. when operands are added using
+/// [`add_readonly_as`](./struct.NpyMultiIterBuilder.html#method.add_readonly_as),
+/// as the elements are then stored in buffers which are overwritten when the iterator advances.
+///
+/// # Example
+///
+/// ```
+/// use numpy::{Casting, NpyMultiIterBuilder, PyArray};
+/// pyo3::Python::with_gil(|py| {
+///     let x = PyArray::arange(py, 0.0_f32, 4.0, 1.0);
+///     let y = PyArray::arange(py, 0_i64, 4, 1);
+///     let mut iter = NpyMultiIterBuilder::new()
+///         .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+///         .add_readwrite_as::<f64, _, _>(y)
+///         .casting(Casting::Unsafe)
+///         .build_lending()
+///         .unwrap();
+///     while let Some((x, y)) = iter.next_item() {
+///         *y += 2.0 * x;
+///     }
+///     drop(iter);
+///     assert_eq!(y.readonly().unwrap().as_slice().unwrap(), &[0, 3, 6, 9]);
+/// });
+/// ```
+pub struct NpyMultiLendingIter<'py, S: MultiIterModeWithManyArrays> {
+    raw: RawIter<'py>,
+    marker: PhantomData<S>,
+}
+
+/// An iterator over the inner loops of multiple arrays which lends each tuple of them
+/// only until the next one is requested, constructed by
+/// [`NpyMultiIterBuilder::build_lending_chunked`](./struct.NpyMultiIterBuilder.html#method.build_lending_chunked).
+///
+/// See [NpyMultiLendingIter](./struct.NpyMultiLendingIter.html) and
+/// [NpyMultiChunkIter](./struct.NpyMultiChunkIter.html) for details.
+///
+/// # Example
+///
+/// ```
+/// use numpy::{NpyMultiIterBuilder, PyArray};
+/// pyo3::Python::with_gil(|py| {
+///     let x = PyArray::arange(py, 0_i32, 10, 1);
+///     let y = PyArray::<f64, _>::zeros(py, 10, false);
+///     let mut iter = NpyMultiIterBuilder::new()
+///         .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+///         .add_readwrite(y)
+///         .build_lending_chunked()
+///         .unwrap();
+///     while let Some((x, mut y)) = iter.next_chunk() {
+///         y.assign(&x);
+///     }
+///     drop(iter);
+///     assert_eq!(y.readonly().unwrap().as_array().sum(), 45.0);
+/// });
+/// ```
+pub struct NpyMultiLendingChunkIter<'py, S: MultiIterModeWithManyArrays> {
+    raw: RawIter<'py>,
+    marker: PhantomData<S>,
+}
+
+/// An iterator which yields the C or Fortran index of each item together with the item,
+/// created by the `with_index` method of [NpySingleIter](./struct.NpySingleIter.html)
+/// or [NpyMultiIter](./struct.NpyMultiIter.html).
+pub struct IndexedIter<I>(I);
+
+/// An iterator which yields the multi-index of each item together with the item,
+/// created by the `with_multi_index` method of [NpySingleIter](./struct.NpySingleIter.html)
+/// or [NpyMultiIter](./struct.NpyMultiIter.html).
+pub struct MultiIndexedIter<I>(I);
+
+macro_rules! impl_index_tracking {
+    ([$($generics: tt)*] $iter: ty) => {
+        impl<'py, $($generics)*> $iter {
+            /// Returns the C or Fortran index of the item most recently returned by `next`
+            /// if [`NpyIterFlag::CIndex`] or [`NpyIterFlag::FIndex`] was set.
+            pub fn index(&self) -> Option<usize> {
+                self.raw.index()
+            }
+
+            /// Returns the multi-index of the item most recently returned by `next`
+            /// if [`NpyIterFlag::MultiIndex`] was set.
+            pub fn multi_index(&self) -> Option<IxDyn> {
+                self.raw.multi_index()
+            }
+
+            /// Moves the iterator such that the next item is the one at the C or Fortran `index`.
+            pub fn goto_index(&mut self, index: usize) -> PyResult<()> {
+                self.raw.goto_index(index)
+            }
+
+            /// Moves the iterator such that the next item is the one at `index`.
+            pub fn goto_multi_index(&mut self, index: &[usize]) -> PyResult<()> {
+                self.raw.goto_multi_index(index)
             }
-        };
 
-        let iternext =
-            match unsafe { PY_ARRAY_API.NpyIter_GetIterNext(iterator.as_mut(), ptr::null_mut()) } {
-                Some(ptr) => ptr,
-                None => {
-                    return Err(PyErr::fetch(py));
+            /// Yields the C or Fortran index of each item together with the item.
+            ///
+            /// Fails unless [`NpyIterFlag::CIndex`] or [`NpyIterFlag::FIndex`] was set.
+            pub fn with_index(self) -> PyResult<IndexedIter<Self>> {
+                if self.raw.index.is_null() {
+                    Err(PyValueError::new_err(
+                        "the iterator does not track a C or Fortran index",
+                    ))
+                } else {
+                    Ok(IndexedIter(self))
                 }
-            };
-        let dataptr = unsafe { PY_ARRAY_API.NpyIter_GetDataPtrArray(iterator.as_mut()) };
+            }
 
-        if dataptr.is_null() {
-            unsafe { PY_ARRAY_API.NpyIter_Deallocate(iterator.as_mut()) };
-            return Err(PyErr::fetch(py));
+            /// Yields the multi-index of each item together with the item.
+            ///
+            /// Fails unless [`NpyIterFlag::MultiIndex`] was set.
+            pub fn with_multi_index(self) -> PyResult<MultiIndexedIter<Self>> {
+                if self.raw.get_multi_index.is_none() {
+                    Err(PyValueError::new_err(
+                        "the iterator does not track a multi-index",
+                    ))
+                } else {
+                    Ok(MultiIndexedIter(self))
+                }
+            }
         }
 
-        let iter_size = unsafe { PY_ARRAY_API.NpyIter_GetIterSize(iterator.as_mut()) };
+        impl<'py, $($generics)*> std::iter::Iterator for IndexedIter<$iter>
+        where
+            $iter: std::iter::Iterator,
+        {
+            type Item = (usize, <$iter as std::iter::Iterator>::Item);
 
-        Ok(Self {
-            iterator,
-            iternext,
-            iter_size,
-            empty: iter_size == 0,
-            dataptr,
-            marker: PhantomData,
-            arrays,
-            was_writables,
-            _py: py,
-        })
+            fn next(&mut self) -> Option<Self::Item> {
+                let item = self.0.next()?;
+                Some((self.0.raw.index().unwrap(), item))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<'py, $($generics)*> std::iter::Iterator for MultiIndexedIter<$iter>
+        where
+            $iter: std::iter::Iterator,
+        {
+            type Item = (IxDyn, <$iter as std::iter::Iterator>::Item);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let item = self.0.next()?;
+                Some((self.0.raw.multi_index().unwrap(), item))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+    };
+}
+
+impl_index_tracking!([T, I] NpySingleIter<'py, T, I>);
+impl_index_tracking!([S: MultiIterModeWithManyArrays] NpyMultiIter<'py, S>);
+
+/// A part of [NpySingleIter](./struct.NpySingleIter.html) or
+/// [NpyMultiIter](./struct.NpyMultiIter.html) over a consecutive range of its items,
+/// created by their `split` method.
+///
+/// In contrast to the iterators themselves, parts can be sent to other threads.
+/// This is sound only if the iteration does not need the Python API, which `split` checks.
+/// Dropping a part acquires the GIL, so other threads using parts must not be joined
+/// while holding it. Use [`Python::allow_threads`] to release the GIL instead.
+///
+/// # Example
+///
+/// ```
+/// use numpy::{NpyIterFlag, NpyMultiIterBuilder, PyArray};
+/// pyo3::Python::with_gil(|py| {
+///     let x = PyArray::arange(py, 0.0, 1000.0, 1.0);
+///     let y = PyArray::<f64, _>::zeros(py, 1000, false);
+///     let parts = NpyMultiIterBuilder::new()
+///         .set(NpyIterFlag::Ranged)
+///         .add_readonly(x.readonly().unwrap())
+///         .add_readwrite(y)
+///         .build()
+///         .unwrap()
+///         .split(4)
+///         .unwrap();
+///     assert_eq!(parts.len(), 4);
+///     // Each part could be handed to a different thread of a pool here,
+///     // e.g. within `py.allow_threads(|| pool.scope(|scope| ...))`.
+///     for part in parts {
+///         for (x, y) in part {
+///             *y = 2.0 * x;
+///         }
+///     }
+///     assert_eq!(y.readonly().unwrap().as_array().sum(), 999.0 * 1000.0);
+/// });
+/// ```
+pub struct NpyIterPart<I> {
+    iter: ManuallyDrop<I>,
+}
+
+// Parts are only created if the iteration does not need the Python API
+// and deallocated while holding the GIL.
+unsafe impl<I: Iterator> Send for NpyIterPart<I> where I::Item: Send {}
+
+impl<I: Iterator> Iterator for NpyIterPart<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
-impl<'py, T, S: MultiIterModeWithManyArrays> Drop for NpyMultiIter<'py, T, S> {
+impl<I> Drop for NpyIterPart<I> {
     fn drop(&mut self) {
-        let _success = unsafe { PY_ARRAY_API.NpyIter_Deallocate(self.iterator.as_mut()) };
-        for (array_ptr, &was_writable) in self.arrays.iter().zip(self.was_writables.iter()) {
-            if was_writable {
-                unsafe { (**array_ptr).flags |= NPY_ARRAY_WRITEABLE };
+        Python::with_gil(|_| unsafe { ManuallyDrop::drop(&mut self.iter) });
+    }
+}
+
+macro_rules! impl_split {
+    ([$($generics: tt)*] $iter: ident < $($params: tt),* >) => {
+        impl<'py, $($generics)*> $iter<'py, $($params),*> {
+            /// Returns whether iterating requires the Python API, e.g. because of object arrays.
+            pub fn needs_api(&self) -> bool {
+                self.raw.needs_api()
+            }
+
+            /// Splits the items not yet returned into at most `n` parts of about equal size
+            /// which can be iterated independently, e.g. on different threads.
+            ///
+            /// This requires [`NpyIterFlag::Ranged`] to be set and fails if
+            /// [`needs_api`](#method.needs_api) is true.
+            pub fn split(self, n: usize) -> PyResult<Vec<NpyIterPart<Self>>> {
+                if self.needs_api() {
+                    return Err(PyValueError::new_err(
+                        "cannot split an iterator which needs the Python API",
+                    ));
+                }
+                let parts = self.raw.split(n)?;
+                Ok(parts
+                    .into_iter()
+                    .map(|raw| NpyIterPart {
+                        iter: ManuallyDrop::new(Self {
+                            raw,
+                            marker: PhantomData,
+                        }),
+                    })
+                    .collect())
+            }
+
+            /// Splits this iterator into one part per thread of the current rayon thread pool
+            /// and returns a parallel iterator over these parts.
+            ///
+            /// Like for [`split`](#method.split), [`NpyIterFlag::Ranged`] must be set.
+            /// The parallel iterator should be consumed within [`Python::allow_threads`].
+            #[cfg(feature = "rayon")]
+            pub fn into_par_iter(self) -> PyResult<NpyParIter<Self>> {
+                Ok(NpyParIter {
+                    parts: self.split(rayon::current_num_threads())?,
+                })
             }
         }
+    };
+}
+
+impl_split!([T, I] NpySingleIter<T, I>);
+impl_split!([S: MultiIterModeWithManyArrays] NpyMultiIter<S>);
+
+/// A parallel iterator over the parts of an iterator, created by the `into_par_iter` method
+/// of [NpySingleIter](./struct.NpySingleIter.html) or [NpyMultiIter](./struct.NpyMultiIter.html).
+///
+/// # Example
+///
+/// ```
+/// use numpy::{NpyIterFlag, NpySingleIterBuilder, PyArray};
+/// use rayon::iter::ParallelIterator;
+/// pyo3::Python::with_gil(|py| {
+///     let array = PyArray::arange(py, 0, 1000, 1);
+///     let iter = NpySingleIterBuilder::readwrite(array)
+///         .set(NpyIterFlag::Ranged)
+///         .build()
+///         .unwrap()
+///         .into_par_iter()
+///         .unwrap();
+///     py.allow_threads(|| iter.for_each(|x| *x *= 2));
+///     assert_eq!(array.readonly().unwrap().as_array().sum(), 999 * 1000);
+/// });
+/// ```
+#[cfg(feature = "rayon")]
+pub struct NpyParIter<I> {
+    parts: Vec<NpyIterPart<I>>,
+}
+
+#[cfg(feature = "rayon")]
+impl<I: Iterator> rayon::iter::ParallelIterator for NpyParIter<I>
+where
+    I::Item: Send,
+{
+    type Item = I::Item;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        use rayon::iter::IntoParallelRefMutIterator;
+        // The parts are borrowed so that they are dropped on the current thread.
+        let mut parts = self.parts;
+        parts
+            .par_iter_mut()
+            .flat_map_iter(|part| part)
+            .drive_unindexed(consumer)
+    }
+}
+
+macro_rules! impl_operand {
+    ($iter: ident) => {
+        impl<'py, S: MultiIterModeWithManyArrays> $iter<'py, S> {
+            /// Returns the `index`-th operand of the iterator, e.g. an output added using
+            /// [`NpyMultiIterBuilder::add_output`](./struct.NpyMultiIterBuilder.html#method.add_output).
+            ///
+            /// The returned array stays valid after the iterator is dropped.
+            pub fn operand<U: Element, D: Dimension>(
+                &self,
+                index: usize,
+            ) -> PyResult<&'py PyArray<U, D>> {
+                self.raw.operand(index)
+            }
+        }
+    };
+}
+
+impl_operand!(NpyMultiIter);
+impl_operand!(NpyMultiChunkIter);
+impl_operand!(NpyMultiLendingIter);
+impl_operand!(NpyMultiLendingChunkIter);
+
+impl<'py, S: MultiIterModeWithManyArrays> NpyMultiLendingIter<'py, S> {
+    /// Returns whether the element of the `operand`-th operand in the item most recently
+    /// returned by `next_item` is visited for the first time, which is always the case
+    /// unless the operand is reduced, c.f. [`NpyMultiIterBuilder::op_axes`].
+    ///
+    /// This is useful to initialize the accumulator of a reduction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numpy::{NpyIterFlag, NpyMultiIterBuilder, PyArray, PyArray1};
+    /// pyo3::Python::with_gil(|py| {
+    ///     let x = PyArray::arange(py, 0.0, 6.0, 1.0).reshape([2, 3]).unwrap();
+    ///     let mut iter = NpyMultiIterBuilder::new()
+    ///         .set(NpyIterFlag::ReduceOk)
+    ///         .add_readonly(x.readonly().unwrap())
+    ///         .add_output::<f64>()
+    ///         .op_axes(1, &[None, Some(0)])
+    ///         .build_lending()
+    ///         .unwrap();
+    ///     let maxima: &PyArray1<f64> = iter.operand(1).unwrap();
+    ///     while iter.next_item().is_some() {
+    ///         let first_visit = iter.is_first_visit(1);
+    ///         let (x, max) = iter.current_item().unwrap();
+    ///         if first_visit || *x > *max {
+    ///             *max = *x;
+    ///         }
+    ///     }
+    ///     assert_eq!(maxima.readonly().unwrap().as_slice().unwrap(), &[3.0, 4.0, 5.0]);
+    /// });
+    /// ```
+    pub fn is_first_visit(&self, operand: usize) -> bool {
+        self.raw.is_first_visit(operand)
     }
 }
 
 macro_rules! impl_multi_iter {
-    ($structure: ty, $($ty: ty)+, $($ptr: ident)+, $expand: ident, $deref: expr) => {
-        impl<'py, T: 'py> std::iter::Iterator for NpyMultiIter<'py, T, $structure> {
-            type Item = ($($ty,)+);
+    ($($op: ident $i: tt)+) => {
+        impl<'py, $($op: OperandItem<'py>,)+> std::iter::Iterator for NpyMultiIter<'py, ($($op,)+)>
+        where
+            ($($op,)+): MultiIterModeWithManyArrays,
+        {
+            type Item = ($($op::Item,)+);
+
             fn next(&mut self) -> Option<Self::Item> {
-                if self.empty {
-                    None
+                if self.raw.advance() {
+                    Some(unsafe { ($($op::item(self.raw.ptr($i)),)+) })
                 } else {
-                    // Note: This pointer is correct and doesn't need to be updated,
-                    // note that we're derefencing a **char into a *char casting to a *T
-                    // and then transforming that into a reference, the value that dataptr
-                    // points to is being updated by iternext to point to the next value.
-                    let ($($ptr,)+) = unsafe { $expand::<T>(self.dataptr) };
-                    let retval = Some(unsafe { $deref });
-                    self.empty = unsafe { (self.iternext)(self.iterator.as_mut()) } == 0;
-                    retval
+                    None
                 }
             }
 
             fn size_hint(&self) -> (usize, Option<usize>) {
-                (self.iter_size as usize, Some(self.iter_size as usize))
+                (self.raw.iter_size as usize, Some(self.raw.iter_size as usize))
+            }
+        }
+
+        impl<'py, $($op: OperandItem<'py>,)+> std::iter::Iterator
+            for NpyMultiChunkIter<'py, ($($op,)+)>
+        where
+            ($($op,)+): MultiIterModeWithManyArrays,
+        {
+            type Item = ($($op::Chunk,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.raw.advance() {
+                    Some(unsafe { ($($op::chunk(self.raw.chunk($i)),)+) })
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<'py, $($op: IterOperand,)+> NpyMultiLendingIter<'py, ($($op,)+)>
+        where
+            ($($op,)+): MultiIterModeWithManyArrays,
+        {
+            /// Returns the next item which is borrowed until the iterator is used again.
+            pub fn next_item<'a>(&'a mut self) -> Option<($(<$op as OperandItem<'a>>::Item,)+)>
+            where
+                $($op: OperandItem<'a>,)+
+            {
+                if self.raw.advance() {
+                    self.current_item()
+                } else {
+                    None
+                }
+            }
+
+            /// Returns the item most recently returned by `next_item` again,
+            /// e.g. after querying [`is_first_visit`](#method.is_first_visit).
+            pub fn current_item<'a>(&'a mut self) -> Option<($(<$op as OperandItem<'a>>::Item,)+)>
+            where
+                $($op: OperandItem<'a>,)+
+            {
+                if self.raw.started && !self.raw.finished {
+                    Some(unsafe { ($(<$op as OperandItem<'a>>::item(self.raw.ptr($i)),)+) })
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<'py, $($op: IterOperand,)+> NpyMultiLendingChunkIter<'py, ($($op,)+)>
+        where
+            ($($op,)+): MultiIterModeWithManyArrays,
+        {
+            /// Returns the next tuple of inner loops which is borrowed until the iterator is used again.
+            pub fn next_chunk<'a>(&'a mut self) -> Option<($(<$op as OperandItem<'a>>::Chunk,)+)>
+            where
+                $($op: OperandItem<'a>,)+
+            {
+                if self.raw.advance() {
+                    Some(unsafe { ($(<$op as OperandItem<'a>>::chunk(self.raw.chunk($i)),)+) })
+                } else {
+                    None
+                }
             }
         }
     };
 }
 
-// Helper functions for conversion
-#[inline(always)]
-unsafe fn expand2<T>(dataptr: *mut *mut c_char) -> (*mut T, *mut T) {
-    (*dataptr as *mut T, *dataptr.offset(1) as *mut T)
-}
-
-#[inline(always)]
-unsafe fn expand3<T>(dataptr: *mut *mut c_char) -> (*mut T, *mut T, *mut T) {
-    (
-        *dataptr as *mut T,
-        *dataptr.offset(1) as *mut T,
-        *dataptr.offset(2) as *mut T,
-    )
-}
-
-impl_multi_iter!(RO<RO<()>>, &'py T &'py T, a b, expand2, (&*a, &*b));
-impl_multi_iter!(RO<RW<()>>, &'py mut T &'py T, a b, expand2, (&mut *a, &*b));
-impl_multi_iter!(RW<RO<()>>, &'py T &'py mut T, a b, expand2, (&*a, &mut *b));
-impl_multi_iter!(RW<RW<()>>, &'py mut T &'py mut T, a b, expand2, (&mut *a, &mut *b));
-impl_multi_iter!(RO<RO<RO<()>>>, &'py T &'py T &'py T, a b c, expand3, (&*a, &*b, &*c));
-impl_multi_iter!(RO<RO<RW<()>>>, &'py mut T &'py T &'py T, a b c, expand3, (&mut *a, &*b, &*c));
-impl_multi_iter!(RO<RW<RO<()>>>, &'py T &'py mut T &'py T, a b c, expand3, (&*a, &mut *b, &*c));
-impl_multi_iter!(RW<RO<RO<()>>>, &'py T &'py T &'py mut T, a b c, expand3, (&*a, &*b, &mut *c));
-impl_multi_iter!(RO<RW<RW<()>>>, &'py mut T &'py mut T &'py T, a b c, expand3, (&mut *a, &mut *b, &*c));
-impl_multi_iter!(RW<RO<RW<()>>>, &'py mut T &'py T &'py mut T, a b c, expand3, (&mut *a, &*b, &mut *c));
-impl_multi_iter!(RW<RW<RO<()>>>, &'py T &'py mut T &'py mut T, a b c, expand3, (&*a, &mut *b, &mut *c));
-impl_multi_iter!(RW<RW<RW<()>>>, &'py mut T &'py mut T &'py mut T, a b c, expand3, (&mut *a, &mut *b, &mut *c));
+impl_multi_iter!(A 0 B 1);
+impl_multi_iter!(A 0 B 1 C 2);
+impl_multi_iter!(A 0 B 1 C 2 D 3);
+impl_multi_iter!(A 0 B 1 C 2 D 3 E 4);
+impl_multi_iter!(A 0 B 1 C 2 D 3 E 4 F 5);
+impl_multi_iter!(A 0 B 1 C 2 D 3 E 4 F 5 G 6);
+impl_multi_iter!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7);
@@ -1,8 +1,9 @@
 //! Readonly arrays
 use crate::npyffi::NPY_ARRAY_WRITEABLE;
-use crate::{Element, NotContiguousError, NpyIndex, PyArray};
+use crate::{Element, NotAlignedError, NotContiguousError, NpyIndex, PyArray};
 use ndarray::{ArrayView, Dimension, Ix1, Ix2, Ix3, Ix4, Ix5, Ix6, IxDyn};
-use pyo3::{prelude::*, types::PyAny, AsPyPointer};
+use pyo3::{prelude::*, types::PyAny, AsPyPointer, PyClass};
+use std::convert::TryFrom;
 
 /// Readonly reference of [`PyArray`](../array/struct.PyArray.html).
 ///
@@ -22,7 +23,7 @@ use pyo3::{prelude::*, types::PyAny, AsPyPointer};
 /// pyo3::Python::with_gil(|py| {
 ///     let py_array = PyArray::arange(py, 0, 4, 1).reshape([2, 2]).unwrap();
 ///     {
-///        let readonly = py_array.readonly();
+///        let readonly = py_array.readonly().unwrap();
 ///        // The internal array is not writeable now.
 ///        pyo3::py_run!(py, py_array, "assert not py_array.flags['WRITEABLE']");
 ///     }
@@ -38,7 +39,7 @@ use pyo3::{prelude::*, types::PyAny, AsPyPointer};
 /// pyo3::Python::with_gil(|py| {
 ///     let py_array = PyArray::arange(py, 0, 4, 1).reshape([2, 2]).unwrap();
 ///     let obj: PyObject = {
-///        let readonly = py_array.readonly();
+///        let readonly = py_array.readonly().unwrap();
 ///        // The internal array is not writeable now.
 ///        pyo3::py_run!(py, py_array, "assert not py_array.flags['WRITEABLE']");
 ///        readonly.into_py(py)
@@ -62,7 +63,7 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     /// use pyo3::types::IntoPyDict;
     /// pyo3::Python::with_gil(|py| {
     ///     let py_array = PyArray::arange(py, 0, 4, 1).reshape([2, 2]).unwrap();
-    ///     let readonly = py_array.readonly();
+    ///     let readonly = py_array.readonly().unwrap();
     ///     assert_eq!(readonly.as_slice().unwrap(), &[0, 1, 2, 3]);
     ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
     ///     let not_contiguous: &PyArray1<i32> = py
@@ -70,11 +71,12 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     ///         .unwrap()
     ///         .downcast()
     ///         .unwrap();
-    ///     assert!(not_contiguous.readonly().as_slice().is_err());
+    ///     assert!(not_contiguous.readonly().unwrap().as_slice().is_err());
     /// });
     /// ```
     pub fn as_slice(&self) -> Result<&[T], NotContiguousError> {
-        unsafe { self.array.as_slice() }
+        // Alignment was checked when creating `self`, so only contiguity can be missing.
+        unsafe { self.array.as_slice() }.map_err(|_| NotContiguousError)
     }
 
     /// Get the immutable view of the internal data of `PyArray`, as
@@ -86,11 +88,12 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     /// use numpy::PyArray;
     /// pyo3::Python::with_gil(|py| {
     ///     let array = PyArray::arange(py, 0, 4, 1).reshape([2, 2]).unwrap();
-    ///     let readonly = array.readonly();
+    ///     let readonly = array.readonly().unwrap();
     ///     assert_eq!(readonly.as_array(), array![[0, 1], [2, 3]]);
     /// });
     /// ```
     pub fn as_array(&self) -> ArrayView<'_, T, D> {
+        // Alignment was checked when creating `self`.
         unsafe { self.array.as_array() }
     }
 
@@ -104,7 +107,7 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     /// ```
     /// use numpy::PyArray;
     /// pyo3::Python::with_gil(|py| {
-    ///     let arr = PyArray::arange(py, 0, 16, 1).reshape([2, 2, 4]).unwrap().readonly();
+    ///     let arr = PyArray::arange(py, 0, 16, 1).reshape([2, 2, 4]).unwrap().readonly().unwrap();
     ///     assert_eq!(*arr.get([1, 0, 3]).unwrap(), 11);
     ///     assert!(arr.get([2, 0, 3]).is_none());
     /// });
@@ -114,7 +117,7 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     /// ```compile_fail
     /// use numpy::PyArray;
     /// pyo3::Python::with_gil(|py| {
-    ///     let arr = PyArray::arange(py, 0, 16, 1).reshape([2, 2, 4]).unwrap().readonly();
+    ///     let arr = PyArray::arange(py, 0, 16, 1).reshape([2, 2, 4]).unwrap().readonly().unwrap();
     ///     let a = arr.get([1, 2]); // Compile Error!
     /// });
     /// ```
@@ -123,8 +126,8 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     /// ```
     /// use numpy::PyArray;
     /// pyo3::Python::with_gil(|py| {
-    ///     let arr = PyArray::arange(py, 0, 16, 1).reshape([2, 2, 4]).unwrap().readonly();
-    ///     let arr = arr.to_dyn().readonly();
+    ///     let arr = PyArray::arange(py, 0, 16, 1).reshape([2, 2, 4]).unwrap().readonly().unwrap();
+    ///     let arr = arr.to_dyn().readonly().unwrap();
     ///     assert!(arr.get([1, 2].as_ref()).is_none());
     /// });
     /// ```
@@ -148,6 +151,17 @@ impl<'py, T: Element, D: Dimension> PyReadonlyArray<'py, T, D> {
     }
 }
 
+impl<'py, D: Dimension> PyReadonlyArray<'py, PyObject, D> {
+    /// Checks that every element of this array is an instance of the Python class `T` and
+    /// returns the elements as [`ndarray::ArrayView`] of `Py<T>`.
+    ///
+    /// See [`PyArray::from_pyclass_array`](../array/struct.PyArray.html#method.from_pyclass_array)
+    /// for an example.
+    pub fn downcast_elements<T: PyClass>(&self) -> PyResult<ArrayView<'_, Py<T>, D>> {
+        unsafe { self.array.downcast_elements() }
+    }
+}
+
 /// One-dimensional readonly array.
 pub type PyReadonlyArray1<'py, T> = PyReadonlyArray<'py, T, Ix1>;
 /// Two-dimensional readonly array.
@@ -166,7 +180,7 @@ pub type PyReadonlyArrayDyn<'py, T> = PyReadonlyArray<'py, T, IxDyn>;
 impl<'py, T: Element, D: Dimension> FromPyObject<'py> for PyReadonlyArray<'py, T, D> {
     fn extract(obj: &'py PyAny) -> PyResult<Self> {
         let array: &PyArray<T, D> = obj.extract()?;
-        Ok(PyReadonlyArray::from(array))
+        Ok(array.readonly()?)
     }
 }
 
@@ -177,8 +191,11 @@ impl<'py, T, D> IntoPy<PyObject> for PyReadonlyArray<'py, T, D> {
     }
 }
 
-impl<'py, T, D> From<&'py PyArray<T, D>> for PyReadonlyArray<'py, T, D> {
-    fn from(array: &'py PyArray<T, D>) -> PyReadonlyArray<'py, T, D> {
+impl<'py, T, D> TryFrom<&'py PyArray<T, D>> for PyReadonlyArray<'py, T, D> {
+    type Error = NotAlignedError;
+
+    fn try_from(array: &'py PyArray<T, D>) -> Result<Self, NotAlignedError> {
+        array.check_aligned()?;
         let flag = array.get_flag();
         let writeable = flag & NPY_ARRAY_WRITEABLE != 0;
         if writeable {
@@ -186,10 +203,10 @@ impl<'py, T, D> From<&'py PyArray<T, D>> for PyReadonlyArray<'py, T, D> {
                 (*array.as_array_ptr()).flags &= !NPY_ARRAY_WRITEABLE;
             }
         }
-        Self {
+        Ok(Self {
             array,
             was_writeable: writeable,
-        }
+        })
     }
 }
 
@@ -0,0 +1,117 @@
+//! NumPy scalars like `np.float32(1.0)` which preserve their exact data type
+use std::mem::{self, MaybeUninit};
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+use pyo3::{
+    exceptions::PyTypeError, AsPyPointer, FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python,
+};
+
+use crate::dtype::{Element, PyArrayDescr};
+use crate::error::TypeError;
+use crate::npyffi::{NPY_TYPES, PY_ARRAY_API};
+
+/// A NumPy scalar whose data type is the one of the element type `T`.
+///
+/// In contrast to extracting `T` itself, extracting `PyScalar<T>` only accepts NumPy scalars
+/// of exactly this data type, e.g. `np.float32` for `PyScalar<f32>`, and converting it into
+/// a Python object creates such a NumPy scalar instead of a built-in Python type like `float`.
+///
+/// # Example
+/// ```
+/// use numpy::PyScalar;
+/// use pyo3::types::IntoPyDict;
+/// use pyo3::IntoPy;
+/// pyo3::Python::with_gil(|py| {
+///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+///     let scalar: PyScalar<f32> = py
+///         .eval("np.float32(1.5)", Some(locals), None)
+///         .unwrap()
+///         .extract()
+///         .unwrap();
+///     assert_eq!(scalar.0, 1.5);
+///     assert!(py.eval("np.float64(1.5)", Some(locals), None).unwrap().extract::<PyScalar<f32>>().is_err());
+///
+///     let result = PyScalar(scalar.0 * 2.0).into_py(py);
+///     pyo3::py_run!(py, result, "import numpy as np; assert type(result) is np.float32 and result == 3.0");
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct PyScalar<T>(pub T);
+
+impl<T> From<T> for PyScalar<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<'py, T: Element + Copy> FromPyObject<'py> for PyScalar<T> {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let py = ob.py();
+        if unsafe { PY_ARRAY_API.PyArray_CheckAnyScalarExact(ob.as_ptr()) } == 0 {
+            return Err(PyTypeError::new_err(format!(
+                "expected a NumPy scalar, but found an object of type {}",
+                ob.get_type().name()?
+            )));
+        }
+        let descr: &PyArrayDescr = unsafe {
+            py.from_owned_ptr_or_err(PY_ARRAY_API.PyArray_DescrFromScalar(ob.as_ptr()) as _)
+        }?;
+        let expected = T::get_dtype(py);
+        if !descr.is_equiv_to(expected) {
+            return Err(TypeError::new(descr, expected).into());
+        }
+        if is_extended(descr) {
+            if descr.itemsize() != mem::size_of::<T>() {
+                return Err(TypeError::new(descr, expected).into());
+            }
+            // For flexible and user-defined types, NumPy writes a pointer to the data of the scalar.
+            let mut data: *mut c_void = ptr::null_mut();
+            unsafe {
+                PY_ARRAY_API.PyArray_ScalarAsCtype(
+                    ob.as_ptr(),
+                    &mut data as *mut *mut c_void as *mut c_void,
+                );
+                Ok(Self(ptr::read_unaligned(data as *const T)))
+            }
+        } else {
+            let mut value = MaybeUninit::<T>::uninit();
+            unsafe {
+                PY_ARRAY_API.PyArray_ScalarAsCtype(ob.as_ptr(), value.as_mut_ptr() as *mut c_void);
+                Ok(Self(value.assume_init()))
+            }
+        }
+    }
+}
+
+/// Mirrors `PyTypeNum_ISEXTENDED`, i.e. checks for flexible and user-defined types.
+fn is_extended(descr: &PyArrayDescr) -> bool {
+    let num = descr.num();
+    (num >= NPY_TYPES::NPY_STRING as c_int && num <= NPY_TYPES::NPY_VOID as c_int)
+        || num >= NPY_TYPES::NPY_USERDEF as c_int
+}
+
+impl<T: Element + Copy> PyScalar<T> {
+    /// Creates the NumPy scalar like [`IntoPy::into_py`] does,
+    /// but returns an error instead of panicking if NumPy fails to create it.
+    pub fn to_scalar<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let mut value = self.0;
+        let descr = T::get_dtype(py);
+        unsafe {
+            let ptr = PY_ARRAY_API.PyArray_Scalar(
+                &mut value as *mut T as *mut c_void,
+                descr.as_dtype_ptr(),
+                ptr::null_mut(),
+            );
+            py.from_owned_ptr_or_err(ptr)
+        }
+    }
+}
+
+impl<T: Element + Copy> IntoPy<PyObject> for PyScalar<T> {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_scalar(py)
+            .expect("NumPy failed to create a scalar")
+            .into()
+    }
+}
@@ -7,7 +7,7 @@ use pyo3::pyclass_slots::PyClassDummySlot;
 use pyo3::type_object::{LazyStaticType, PyTypeInfo};
 use pyo3::{ffi, types::PyAny, PyCell};
 
-/// Utility type to safely store Box<[_]> or Vec<_> on the Python heap
+/// Utility type to safely store Box<[_]>, Vec<_> or another owner of a slice on the Python heap
 pub(crate) struct PySliceContainer {
     ptr: *mut u8,
     len: usize,
@@ -75,6 +75,32 @@ where
     }
 }
 
+/// Wrapper for a boxed value which owns the data of a slice, e.g. an `Arc<[_]>`
+///
+/// The owner is boxed before the slice is borrowed from it,
+/// so that the data stays at the same address even if it is stored inline.
+pub(crate) struct SliceOwner<O>(pub(crate) Box<O>);
+
+impl<O: Send + 'static> From<SliceOwner<O>> for PySliceContainer {
+    fn from(owner: SliceOwner<O>) -> Self {
+        unsafe fn drop_owner<O>(ptr: *mut u8, _len: usize, _cap: usize) {
+            let _ = Box::from_raw(ptr as *mut O);
+        }
+
+        let ptr = Box::into_raw(owner.0) as *mut u8;
+        let len = 0;
+        let cap = 0;
+        let drop = drop_owner::<O>;
+
+        Self {
+            ptr,
+            len,
+            cap,
+            drop,
+        }
+    }
+}
+
 impl Drop for PySliceContainer {
     fn drop(&mut self) {
         unsafe {
@@ -90,7 +116,8 @@ impl PyClass for PySliceContainer {
 }
 
 impl PyClassImpl for PySliceContainer {
-    const DOC: &'static str = "Memory store for a PyArray backed by a Box<[_]> or a Vec<_> \0";
+    const DOC: &'static str =
+        "Memory store for a PyArray backed by a Box<[_]>, a Vec<_> or another owner \0";
 
     type BaseType = PyAny;
     type Layout = PyCell<Self>;
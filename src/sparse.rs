@@ -0,0 +1,386 @@
+//! Extracting and creating sparse matrices of [`scipy.sparse`][sparse]
+//!
+//! The extractors borrow the arrays storing a sparse matrix as [`PyReadonlyArray1`]s without
+//! copying them, validating their element and index types as well as their lengths.
+//! The `create` functions build sparse matrices from Rust vectors which are moved into
+//! NumPy arrays using [`IntoPyArray`].
+//!
+//! Note that SciPy chooses the width of index arrays depending on the size of a matrix,
+//! so index arrays of newly created matrices may be converted to another [`SparseIndex`] type.
+//!
+//! # Example
+//! ```
+//! use numpy::sparse::PyCsrMatrix;
+//! pyo3::Python::with_gil(|py| {
+//!     let matrix = PyCsrMatrix::<f64>::create(
+//!         py,
+//!         (2, 3),
+//!         vec![1.0, 2.0, 3.0],
+//!         vec![0, 2, 1],
+//!         vec![0, 2, 3],
+//!     )
+//!     .unwrap();
+//!     pyo3::py_run!(py, matrix, "assert matrix.toarray().tolist() == [[1, 0, 2], [0, 3, 0]]");
+//!
+//!     let csr: PyCsrMatrix<f64> = matrix.extract().unwrap();
+//!     assert_eq!(csr.shape(), (2, 3));
+//!     assert_eq!(csr.row(1), (&[1][..], &[3.0][..]));
+//! });
+//! ```
+//!
+//! [sparse]: https://docs.scipy.org/doc/scipy/reference/sparse.html
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    types::{PyDict, PyModule},
+    FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult, Python,
+};
+
+use crate::convert::IntoPyArray;
+use crate::dtype::Element;
+use crate::PyReadonlyArray1;
+
+/// Integer types which SciPy uses for the index arrays of sparse matrices,
+/// i.e. `i32` and `i64`.
+pub trait SparseIndex: Element + Copy {
+    /// Converts the index into `usize`, returning `None` if it is negative.
+    fn to_usize(self) -> Option<usize>;
+}
+
+impl SparseIndex for i32 {
+    fn to_usize(self) -> Option<usize> {
+        if self < 0 {
+            None
+        } else {
+            Some(self as usize)
+        }
+    }
+}
+
+impl SparseIndex for i64 {
+    fn to_usize(self) -> Option<usize> {
+        if self < 0 {
+            None
+        } else {
+            Some(self as usize)
+        }
+    }
+}
+
+macro_rules! impl_compressed_matrix {
+    (
+        $(#[$attr: meta])*
+        $name: ident,
+        $format: literal,
+        $class: literal,
+        $create_doc: literal,
+        $indices_doc: literal,
+        $indptr_doc: literal,
+        $(#[$major_attr: meta])*
+        $major_fn: ident,
+        $axis: tt
+    ) => {
+        $(#[$attr])*
+        pub struct $name<'py, T, I = i32> {
+            matrix: &'py PyAny,
+            shape: (usize, usize),
+            data: PyReadonlyArray1<'py, T>,
+            indices: PyReadonlyArray1<'py, I>,
+            indptr: PyReadonlyArray1<'py, I>,
+        }
+
+        impl<'py, T: Element, I: SparseIndex> $name<'py, T, I> {
+            #[doc = $create_doc]
+            pub fn create(
+                py: Python<'py>,
+                shape: (usize, usize),
+                data: Vec<T>,
+                indices: Vec<I>,
+                indptr: Vec<I>,
+            ) -> PyResult<&'py PyAny> {
+                let components = (
+                    data.into_pyarray(py),
+                    indices.into_pyarray(py),
+                    indptr.into_pyarray(py),
+                );
+                create_matrix(py, $class, components, shape)
+            }
+
+            /// Returns the underlying SciPy object.
+            pub fn as_any(&self) -> &'py PyAny {
+                self.matrix
+            }
+
+            /// Returns the shape of the matrix as `(rows, columns)`.
+            pub fn shape(&self) -> (usize, usize) {
+                self.shape
+            }
+
+            /// Returns the number of stored elements, including explicit zeros.
+            pub fn nnz(&self) -> usize {
+                self.data.len()
+            }
+
+            /// Returns the values of the stored elements.
+            pub fn data(&self) -> &PyReadonlyArray1<'py, T> {
+                &self.data
+            }
+
+            #[doc = $indices_doc]
+            pub fn indices(&self) -> &PyReadonlyArray1<'py, I> {
+                &self.indices
+            }
+
+            #[doc = $indptr_doc]
+            pub fn indptr(&self) -> &PyReadonlyArray1<'py, I> {
+                &self.indptr
+            }
+
+            $(#[$major_attr])*
+            ///
+            /// # Panics
+            /// Panics if `i` is out of bounds.
+            pub fn $major_fn(&self, i: usize) -> (&[I], &[T]) {
+                let indptr = self.indptr.as_slice().unwrap();
+                let start = indptr[i].to_usize().unwrap();
+                let end = indptr[i + 1].to_usize().unwrap();
+                (
+                    &self.indices.as_slice().unwrap()[start..end],
+                    &self.data.as_slice().unwrap()[start..end],
+                )
+            }
+        }
+
+        impl<'py, T: Element, I: SparseIndex> FromPyObject<'py> for $name<'py, T, I> {
+            fn extract(ob: &'py PyAny) -> PyResult<Self> {
+                check_format(ob, $format)?;
+                let shape: (usize, usize) = ob.getattr("shape")?.extract()?;
+                let data: PyReadonlyArray1<T> = ob.getattr("data")?.extract()?;
+                let indices: PyReadonlyArray1<I> = ob.getattr("indices")?.extract()?;
+                let indptr: PyReadonlyArray1<I> = ob.getattr("indptr")?.extract()?;
+                if indptr.len() != shape.$axis + 1 {
+                    return Err(length_error("indptr", indptr.len(), shape.$axis + 1));
+                }
+                if indices.len() != data.len() {
+                    return Err(length_error("indices", indices.len(), data.len()));
+                }
+                check_offsets(&indptr, data.len())?;
+                // Rows and columns are returned as slices of these arrays.
+                check_contiguous("data", &data)?;
+                check_contiguous("indices", &indices)?;
+                check_contiguous("indptr", &indptr)?;
+                Ok(Self {
+                    matrix: ob,
+                    shape,
+                    data,
+                    indices,
+                    indptr,
+                })
+            }
+        }
+
+        impl<'py, T, I> IntoPy<PyObject> for $name<'py, T, I> {
+            fn into_py(self, py: Python<'_>) -> PyObject {
+                self.matrix.into_py(py)
+            }
+        }
+    };
+}
+
+impl_compressed_matrix!(
+    /// A sparse matrix in the `csr` format of `scipy.sparse`.
+    ///
+    /// The column indices of the stored elements of row `i` are `indices[indptr[i]..indptr[i + 1]]`
+    /// and their values are stored in the same range of `data`.
+    PyCsrMatrix,
+    "csr",
+    "csr_matrix",
+    "Creates a `scipy.sparse.csr_matrix` of the given shape from its components.",
+    "Returns the column indices of the stored elements.",
+    "Returns the offsets of each row into `indices` and `data`.",
+    /// Returns the column indices and values of the stored elements of row `i`.
+    row,
+    0
+);
+
+impl_compressed_matrix!(
+    /// A sparse matrix in the `csc` format of `scipy.sparse`.
+    ///
+    /// The row indices of the stored elements of column `i` are `indices[indptr[i]..indptr[i + 1]]`
+    /// and their values are stored in the same range of `data`.
+    PyCscMatrix,
+    "csc",
+    "csc_matrix",
+    "Creates a `scipy.sparse.csc_matrix` of the given shape from its components.",
+    "Returns the row indices of the stored elements.",
+    "Returns the offsets of each column into `indices` and `data`.",
+    /// Returns the row indices and values of the stored elements of column `i`.
+    column,
+    1
+);
+
+/// A sparse matrix in the `coo` format of `scipy.sparse`.
+///
+/// The `k`-th stored element has the value `data[k]` at the position `(row[k], col[k])`.
+/// Note that positions are neither sorted nor unique.
+pub struct PyCooMatrix<'py, T, I = i32> {
+    matrix: &'py PyAny,
+    shape: (usize, usize),
+    data: PyReadonlyArray1<'py, T>,
+    row: PyReadonlyArray1<'py, I>,
+    col: PyReadonlyArray1<'py, I>,
+}
+
+impl<'py, T: Element, I: SparseIndex> PyCooMatrix<'py, T, I> {
+    /// Creates a `scipy.sparse.coo_matrix` of the given shape from its components.
+    pub fn create(
+        py: Python<'py>,
+        shape: (usize, usize),
+        data: Vec<T>,
+        row: Vec<I>,
+        col: Vec<I>,
+    ) -> PyResult<&'py PyAny> {
+        let components = (
+            data.into_pyarray(py),
+            (row.into_pyarray(py), col.into_pyarray(py)),
+        );
+        create_matrix(py, "coo_matrix", components, shape)
+    }
+
+    /// Returns the underlying SciPy object.
+    pub fn as_any(&self) -> &'py PyAny {
+        self.matrix
+    }
+
+    /// Returns the shape of the matrix as `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// Returns the number of stored elements, including explicit zeros and duplicates.
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the values of the stored elements.
+    pub fn data(&self) -> &PyReadonlyArray1<'py, T> {
+        &self.data
+    }
+
+    /// Returns the row indices of the stored elements.
+    pub fn row(&self) -> &PyReadonlyArray1<'py, I> {
+        &self.row
+    }
+
+    /// Returns the column indices of the stored elements.
+    pub fn col(&self) -> &PyReadonlyArray1<'py, I> {
+        &self.col
+    }
+
+    /// Iterates over the stored elements as `(row, column, value)`.
+    pub fn iter(&self) -> impl Iterator<Item = (I, I, &T)> + '_ {
+        self.row
+            .as_array()
+            .into_iter()
+            .zip(self.col.as_array())
+            .zip(self.data.as_array())
+            .map(|((&row, &col), value)| (row, col, value))
+    }
+}
+
+impl<'py, T: Element, I: SparseIndex> FromPyObject<'py> for PyCooMatrix<'py, T, I> {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        check_format(ob, "coo")?;
+        let shape = ob.getattr("shape")?.extract()?;
+        let data: PyReadonlyArray1<T> = ob.getattr("data")?.extract()?;
+        let row: PyReadonlyArray1<I> = ob.getattr("row")?.extract()?;
+        let col: PyReadonlyArray1<I> = ob.getattr("col")?.extract()?;
+        if row.len() != data.len() {
+            return Err(length_error("row", row.len(), data.len()));
+        }
+        if col.len() != data.len() {
+            return Err(length_error("col", col.len(), data.len()));
+        }
+        Ok(Self {
+            matrix: ob,
+            shape,
+            data,
+            row,
+            col,
+        })
+    }
+}
+
+impl<'py, T, I> IntoPy<PyObject> for PyCooMatrix<'py, T, I> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.matrix.into_py(py)
+    }
+}
+
+fn check_format(ob: &PyAny, expected: &str) -> PyResult<()> {
+    let format = match ob.getattr("format") {
+        Ok(format) => format.extract::<&str>()?,
+        Err(_) => {
+            return Err(PyTypeError::new_err(format!(
+                "expected a scipy.sparse matrix, but found an object of type {}",
+                ob.get_type().name()?
+            )))
+        }
+    };
+    if format != expected {
+        return Err(PyTypeError::new_err(format!(
+            "expected a sparse matrix in the {} format, but found the {} format",
+            expected, format
+        )));
+    }
+    Ok(())
+}
+
+fn check_offsets<I: SparseIndex>(indptr: &PyReadonlyArray1<I>, nnz: usize) -> PyResult<()> {
+    let indptr = indptr.as_array();
+    let mut valid = indptr.iter().next().and_then(|first| first.to_usize()) == Some(0);
+    let mut last = 0;
+    for offset in indptr.iter() {
+        match offset.to_usize() {
+            Some(offset) if offset >= last => last = offset,
+            _ => valid = false,
+        }
+    }
+    if !valid || last != nnz {
+        return Err(PyValueError::new_err(
+            "malformed sparse matrix: indptr must be non-decreasing from zero to the number of stored elements",
+        ));
+    }
+    Ok(())
+}
+
+fn check_contiguous<T: Element>(name: &str, array: &PyReadonlyArray1<T>) -> PyResult<()> {
+    if array.is_contiguous() {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(format!(
+            "malformed sparse matrix: {} is not contiguous",
+            name
+        )))
+    }
+}
+
+fn length_error(name: &str, len: usize, expected: usize) -> PyErr {
+    PyValueError::new_err(format!(
+        "malformed sparse matrix: {} has length {}, but {} was expected",
+        name, len, expected
+    ))
+}
+
+fn create_matrix<'py>(
+    py: Python<'py>,
+    class: &str,
+    components: impl IntoPy<PyObject>,
+    shape: (usize, usize),
+) -> PyResult<&'py PyAny> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("shape", shape)?;
+    kwargs.set_item("copy", false)?;
+    PyModule::import(py, "scipy.sparse")?
+        .getattr(class)?
+        .call((components,), Some(kwargs))
+}
@@ -1,8 +1,16 @@
-use crate::npyffi::{NPY_CASTING, NPY_ORDER};
-use crate::{Element, PyArray, PY_ARRAY_API};
-use ndarray::{Dimension, IxDyn};
-use pyo3::{AsPyPointer, FromPyPointer, PyAny, PyNativeType, PyResult};
-use std::ffi::CStr;
+use crate::npyffi::{self, NPY_ORDER};
+use crate::{Casting, Element, PyArray, PyScalar, PY_ARRAY_API};
+use ndarray::{Dimension, Ix0, Ix1, Ix2, Ix3, Ix4, Ix5, Ix6, IxDyn};
+use pyo3::{
+    exceptions::PyValueError,
+    once_cell::GILOnceCell,
+    types::{PyDict, PyModule, PyTuple},
+    AsPyPointer, FromPyPointer, IntoPy, Py, PyAny, PyNativeType, PyObject, PyResult, Python,
+};
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::ptr;
 
 /// Return the inner product of two arrays.
 ///
@@ -40,7 +48,7 @@ where
 ///     let b = numpy::pyarray![py, [4, 1], [2, 2]];
 ///     let dot: &numpy::PyArray2::<_> = numpy::dot(a, b).unwrap();
 ///     assert_eq!(
-///         dot.readonly().as_array(),
+///         dot.readonly().unwrap().as_array(),
 ///         ndarray::array![[4, 1], [2, 2]]
 ///     );
 /// });
@@ -62,9 +70,374 @@ where
     obj.extract()
 }
 
+/// Return the matrix product of two arrays like
+/// [`numpy.matmul`](https://numpy.org/doc/stable/reference/generated/numpy.matmul.html).
+///
+/// Arrays with more than two dimensions are treated as stacks of matrices
+/// whose leading batch dimensions are broadcast against each other.
+/// The dimensionality of the result is determined by [`MatmulDim`].
+///
+/// # Example
+/// ```
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::PyArray::arange(py, 0, 8, 1).reshape([2, 2, 2]).unwrap();
+///     let b = numpy::pyarray![py, [1, 0], [0, 2]];
+///     let c = numpy::matmul(a, b).unwrap();
+///     assert_eq!(
+///         c.readonly().unwrap().as_array(),
+///         ndarray::array![[[0, 2], [2, 6]], [[4, 10], [6, 14]]]
+///     );
+/// });
+/// ```
+pub fn matmul<'py, T, DIN1, DIN2>(
+    array1: &'py PyArray<T, DIN1>,
+    array2: &'py PyArray<T, DIN2>,
+) -> PyResult<&'py PyArray<T, <DIN1 as MatmulDim<DIN2>>::Output>>
+where
+    DIN1: MatmulDim<DIN2>,
+    DIN2: Dimension,
+    T: Element,
+{
+    let py = array1.py();
+    let obj = if agrees_with_dot(array1.ndim(), array2.ndim()) {
+        matrix_product(py, array1, array2, ptr::null_mut())?
+    } else {
+        call_numpy(py, &MATMUL, "matmul", (array1, array2), None)?
+    };
+    as_array_result(obj)
+}
+
+/// Return the matrix product of two arrays like [`matmul`], but writes it into `out`
+/// which is returned on success.
+pub fn matmul_into<'py, T, DIN1, DIN2>(
+    array1: &'py PyArray<T, DIN1>,
+    array2: &'py PyArray<T, DIN2>,
+    out: &'py PyArray<T, <DIN1 as MatmulDim<DIN2>>::Output>,
+) -> PyResult<&'py PyArray<T, <DIN1 as MatmulDim<DIN2>>::Output>>
+where
+    DIN1: MatmulDim<DIN2>,
+    DIN2: Dimension,
+    T: Element,
+{
+    let py = array1.py();
+    // `PyArray_MatrixProduct2` only accepts C-contiguous outputs.
+    if agrees_with_dot(array1.ndim(), array2.ndim()) && out.is_c_contiguous() {
+        matrix_product(py, array1, array2, out.as_array_ptr())?;
+    } else {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("out", out)?;
+        call_numpy(py, &MATMUL, "matmul", (array1, array2), Some(kwargs))?;
+    }
+    Ok(out)
+}
+
+/// The dimensionality of the result of [`matmul`] for operands of the dimensionalities `Self` and `D`.
+///
+/// One-dimensional operands are treated as vectors whose dimension is removed from the result,
+/// otherwise the result has as many dimensions as the larger operand.
+/// The result is dynamic-dimensional if any operand is.
+pub trait MatmulDim<D: Dimension>: Dimension {
+    /// The dimensionality of the result.
+    type Output: Dimension;
+}
+
+macro_rules! impl_matmul_dim {
+    ($(($lhs: ty, $rhs: ty) => $out: ty,)+) => {
+        $(
+            impl MatmulDim<$rhs> for $lhs {
+                type Output = $out;
+            }
+        )+
+    };
+}
+
+impl_matmul_dim!(
+    (Ix1, Ix1) => Ix0,
+    (Ix1, Ix2) => Ix1,
+    (Ix1, Ix3) => Ix2,
+    (Ix1, Ix4) => Ix3,
+    (Ix1, Ix5) => Ix4,
+    (Ix1, Ix6) => Ix5,
+    (Ix2, Ix1) => Ix1,
+    (Ix2, Ix2) => Ix2,
+    (Ix2, Ix3) => Ix3,
+    (Ix2, Ix4) => Ix4,
+    (Ix2, Ix5) => Ix5,
+    (Ix2, Ix6) => Ix6,
+    (Ix3, Ix1) => Ix2,
+    (Ix3, Ix2) => Ix3,
+    (Ix3, Ix3) => Ix3,
+    (Ix3, Ix4) => Ix4,
+    (Ix3, Ix5) => Ix5,
+    (Ix3, Ix6) => Ix6,
+    (Ix4, Ix1) => Ix3,
+    (Ix4, Ix2) => Ix4,
+    (Ix4, Ix3) => Ix4,
+    (Ix4, Ix4) => Ix4,
+    (Ix4, Ix5) => Ix5,
+    (Ix4, Ix6) => Ix6,
+    (Ix5, Ix1) => Ix4,
+    (Ix5, Ix2) => Ix5,
+    (Ix5, Ix3) => Ix5,
+    (Ix5, Ix4) => Ix5,
+    (Ix5, Ix5) => Ix5,
+    (Ix5, Ix6) => Ix6,
+    (Ix6, Ix1) => Ix5,
+    (Ix6, Ix2) => Ix6,
+    (Ix6, Ix3) => Ix6,
+    (Ix6, Ix4) => Ix6,
+    (Ix6, Ix5) => Ix6,
+    (Ix6, Ix6) => Ix6,
+    (IxDyn, Ix1) => IxDyn,
+    (IxDyn, Ix2) => IxDyn,
+    (IxDyn, Ix3) => IxDyn,
+    (IxDyn, Ix4) => IxDyn,
+    (IxDyn, Ix5) => IxDyn,
+    (IxDyn, Ix6) => IxDyn,
+    (IxDyn, IxDyn) => IxDyn,
+    (Ix1, IxDyn) => IxDyn,
+    (Ix2, IxDyn) => IxDyn,
+    (Ix3, IxDyn) => IxDyn,
+    (Ix4, IxDyn) => IxDyn,
+    (Ix5, IxDyn) => IxDyn,
+    (Ix6, IxDyn) => IxDyn,
+);
+
+/// Return the tensor dot product of two arrays summing over the given axes like
+/// [`numpy.tensordot`](https://numpy.org/doc/stable/reference/generated/numpy.tensordot.html).
+///
+/// The `k`-th axis in `axes.0` of `array1` is contracted with the `k`-th axis in `axes.1` of `array2`.
+///
+/// # Example
+/// ```
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::PyArray::arange(py, 0, 6, 1).reshape([1, 2, 3]).unwrap();
+///     let b = numpy::pyarray![py, [1, 1, 1], [1, 1, 1]];
+///     let c: &numpy::PyArray1<_> = numpy::tensordot(a, b, (&[1, 2], &[0, 1])).unwrap();
+///     assert_eq!(c.readonly().unwrap().as_slice().unwrap(), &[15]);
+/// });
+/// ```
+pub fn tensordot<'py, T, DIN1, DIN2, DOUT>(
+    array1: &'py PyArray<T, DIN1>,
+    array2: &'py PyArray<T, DIN2>,
+    axes: (&[usize], &[usize]),
+) -> PyResult<&'py PyArray<T, DOUT>>
+where
+    DIN1: Dimension,
+    DIN2: Dimension,
+    DOUT: Dimension,
+    T: Element,
+{
+    if axes.0.len() != axes.1.len() {
+        return Err(PyValueError::new_err(format!(
+            "tensordot requires the same number of axes for both arrays, but got {} and {}",
+            axes.0.len(),
+            axes.1.len()
+        )));
+    }
+    let axes = (axes.0.to_vec(), axes.1.to_vec());
+    let obj = call_numpy(
+        array1.py(),
+        &TENSORDOT,
+        "tensordot",
+        (array1, array2, axes),
+        None,
+    )?;
+    as_array_result(obj)
+}
+
+/// Return the outer product of two arrays like
+/// [`numpy.outer`](https://numpy.org/doc/stable/reference/generated/numpy.outer.html),
+/// flattening arrays with more than one dimension.
+///
+/// # Example
+/// ```
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::pyarray![py, 1, 2];
+///     let b = numpy::pyarray![py, 1, 10, 100];
+///     let c = numpy::outer(a, b).unwrap();
+///     assert_eq!(c.readonly().unwrap().as_array(), ndarray::array![[1, 10, 100], [2, 20, 200]]);
+/// });
+/// ```
+pub fn outer<'py, T, DIN1, DIN2>(
+    array1: &'py PyArray<T, DIN1>,
+    array2: &'py PyArray<T, DIN2>,
+) -> PyResult<&'py PyArray<T, Ix2>>
+where
+    DIN1: Dimension,
+    DIN2: Dimension,
+    T: Element,
+{
+    let obj = call_numpy(array1.py(), &OUTER, "outer", (array1, array2), None)?;
+    as_array_result(obj)
+}
+
+/// Return the dot product of two flattened arrays like
+/// [`numpy.vdot`](https://numpy.org/doc/stable/reference/generated/numpy.vdot.html),
+/// using the complex conjugate of the elements of `array1`.
+///
+/// # Example
+/// ```
+/// use numpy::Complex64;
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::pyarray![py, Complex64::new(1.0, 2.0), Complex64::new(3.0, 4.0)];
+///     let b = numpy::pyarray![py, Complex64::new(5.0, 6.0), Complex64::new(7.0, 8.0)];
+///     assert_eq!(numpy::vdot(a, b).unwrap(), Complex64::new(70.0, -8.0));
+/// });
+/// ```
+pub fn vdot<'py, T, DIN1, DIN2>(
+    array1: &'py PyArray<T, DIN1>,
+    array2: &'py PyArray<T, DIN2>,
+) -> PyResult<T>
+where
+    DIN1: Dimension,
+    DIN2: Dimension,
+    T: Element + Copy,
+{
+    let obj = call_numpy(array1.py(), &VDOT, "vdot", (array1, array2), None)?;
+    Ok(obj.extract::<PyScalar<T>>()?.0)
+}
+
+/// Return the Kronecker product of two arrays like
+/// [`numpy.kron`](https://numpy.org/doc/stable/reference/generated/numpy.kron.html).
+///
+/// # Example
+/// ```
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::pyarray![py, [1, 2], [3, 4]];
+///     let b = numpy::pyarray![py, [1, 1]];
+///     let c = numpy::kron(a, b).unwrap();
+///     assert_eq!(c.readonly().unwrap().as_array(), ndarray::array![[1, 1, 2, 2], [3, 3, 4, 4]]);
+/// });
+/// ```
+pub fn kron<'py, T, D>(
+    array1: &'py PyArray<T, D>,
+    array2: &'py PyArray<T, D>,
+) -> PyResult<&'py PyArray<T, D>>
+where
+    D: Dimension,
+    T: Element,
+{
+    let obj = call_numpy(array1.py(), &KRON, "kron", (array1, array2), None)?;
+    as_array_result(obj)
+}
+
+/// Return the sum along the diagonal of the two-dimensional sub-arrays given by `axis1` and `axis2`,
+/// offset from the main diagonal by `offset`, with the element type of the array.
+///
+/// # Example
+/// ```
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::pyarray![py, [1, 2, 3], [4, 5, 6], [7, 8, 9]];
+///     assert_eq!(numpy::trace(a, 0, 0, 1).unwrap().item(), 15);
+///     assert_eq!(numpy::trace(a, 1, 0, 1).unwrap().item(), 8);
+/// });
+/// ```
+pub fn trace<'py, T, D>(
+    array: &'py PyArray<T, D>,
+    offset: isize,
+    axis1: usize,
+    axis2: usize,
+) -> PyResult<&'py PyArray<T, <D::Smaller as Dimension>::Smaller>>
+where
+    D: Dimension,
+    T: Element,
+{
+    let py = array.py();
+    let offset = c_int::try_from(offset)
+        .map_err(|_| PyValueError::new_err(format!("trace offset {} is out of range", offset)))?;
+    let axis = |axis: usize| {
+        c_int::try_from(axis)
+            .map_err(|_| PyValueError::new_err(format!("trace axis {} is out of range", axis)))
+    };
+    let (axis1, axis2) = (axis(axis1)?, axis(axis2)?);
+    let obj = unsafe {
+        let result = PY_ARRAY_API.PyArray_Trace(
+            array.as_array_ptr(),
+            offset,
+            axis1,
+            axis2,
+            T::get_dtype(py).num(),
+            ptr::null_mut(),
+        );
+        PyAny::from_owned_ptr_or_err(py, result)?
+    };
+    as_array_result(obj)
+}
+
+static MATMUL: GILOnceCell<PyObject> = GILOnceCell::new();
+static TENSORDOT: GILOnceCell<PyObject> = GILOnceCell::new();
+static OUTER: GILOnceCell<PyObject> = GILOnceCell::new();
+static VDOT: GILOnceCell<PyObject> = GILOnceCell::new();
+static KRON: GILOnceCell<PyObject> = GILOnceCell::new();
+
+/// Calls the NumPy function `name` which is looked up only once and then cached in `function`.
+fn call_numpy<'py>(
+    py: Python<'py>,
+    function: &'static GILOnceCell<PyObject>,
+    name: &str,
+    args: impl IntoPy<Py<PyTuple>>,
+    kwargs: Option<&PyDict>,
+) -> PyResult<&'py PyAny> {
+    let function = match function.get(py) {
+        Some(function) => function,
+        None => {
+            let value = PyModule::import(py, "numpy")?.getattr(name)?.into();
+            // Another thread might have set the cell while the import released the GIL.
+            let _ = function.set(py, value);
+            function.get(py).unwrap()
+        }
+    };
+    function.as_ref(py).call(args, kwargs)
+}
+
+/// Checks whether `numpy.matmul` computes the same as `numpy.dot` for operands of these dimensionalities,
+/// which excludes zero-dimensional operands that `numpy.matmul` rejects.
+fn agrees_with_dot(ndim1: usize, ndim2: usize) -> bool {
+    (1..=2).contains(&ndim1) && (1..=2).contains(&ndim2)
+}
+
+/// Computes `numpy.dot` via the C-API, writing into `out` unless it is null.
+fn matrix_product<'py>(
+    py: Python<'py>,
+    array1: &PyAny,
+    array2: &PyAny,
+    out: *mut npyffi::PyArrayObject,
+) -> PyResult<&'py PyAny> {
+    unsafe {
+        let result = PY_ARRAY_API.PyArray_MatrixProduct2(array1.as_ptr(), array2.as_ptr(), out);
+        PyAny::from_owned_ptr_or_err(py, result)
+    }
+}
+
+/// Extracts the result of a NumPy function, converting scalars into zero-dimensional arrays.
+fn as_array_result<'py, T, D>(obj: &'py PyAny) -> PyResult<&'py PyArray<T, D>>
+where
+    D: Dimension,
+    T: Element,
+{
+    if unsafe { npyffi::PyArray_Check(obj.as_ptr()) } != 0 {
+        return obj.extract();
+    }
+    // Unlike `PyArray_FromScalar`, this also wraps the Python objects returned for object arrays.
+    let array = unsafe {
+        let result = PY_ARRAY_API.PyArray_FromAny(
+            obj.as_ptr(),
+            T::get_dtype(obj.py()).into_dtype_ptr(),
+            0,
+            0,
+            0,
+            ptr::null_mut(),
+        );
+        PyAny::from_owned_ptr_or_err(obj.py(), result)?
+    };
+    array.extract()
+}
+
 /// Return the Einstein summation convention of given tensors.
 ///
-/// We also provide the [einsum macro](./macro.einsum.html).
+/// We also provide the [einsum macro](./macro.einsum.html) and the [`Einsum`] builder
+/// which supports operands of different types and dimensionalities.
 pub fn einsum_impl<'py, T, DOUT>(
     subscripts: &str,
     arrays: &[&'py PyArray<T, IxDyn>],
@@ -73,24 +446,144 @@ where
     DOUT: Dimension,
     T: Element,
 {
-    let subscripts: std::borrow::Cow<CStr> = match CStr::from_bytes_with_nul(subscripts.as_bytes())
+    let subscripts = subscripts.strip_suffix('\0').unwrap_or(subscripts);
+    let mut einsum = Einsum::new(subscripts)
+        .casting(Casting::No)
+        .order(NPY_ORDER::NPY_KEEPORDER);
+    for array in arrays {
+        einsum = einsum.operand(*array);
+    }
+    einsum.call()
+}
+
+/// A builder for the Einstein summation convention of given tensors
+/// like [`numpy.einsum`](https://numpy.org/doc/stable/reference/generated/numpy.einsum.html).
+///
+/// In contrast to the [einsum macro](./macro.einsum.html), the subscripts can be computed at runtime
+/// and the operands can have different element types and dimensionalities.
+/// The result has the element type `T` requested when calling [`call`](#method.call)
+/// and the operands are cast to it according to [`casting`](#method.casting),
+/// which defaults to [`Casting::Safe`] like in NumPy.
+///
+/// # Example
+/// ```
+/// use numpy::{Einsum, PyArray1, PyArray2};
+/// pyo3::Python::with_gil(|py| {
+///     let a = numpy::pyarray![py, [1, 2], [3, 4]];
+///     let b = numpy::pyarray![py, 0.5, 1.5];
+///     let subscripts = format!("{}j,j->{}", "i", "i");
+///     let result: &PyArray1<f64> = Einsum::new(&subscripts).operand(a).operand(b).call().unwrap();
+///     assert_eq!(result.readonly().unwrap().as_slice().unwrap(), &[3.5, 7.5]);
+///
+///     let out = PyArray2::<i32>::zeros(py, [2, 2], false);
+///     Einsum::new("ij->ji").operand(a).out(out).call::<i32, ndarray::Ix2>().unwrap();
+///     assert_eq!(out.readonly().unwrap().as_array(), ndarray::array![[1, 3], [2, 4]]);
+///
+///     assert!(Einsum::new("ij,jk").operand(a).call::<i32, ndarray::Ix2>().is_err());
+/// });
+/// ```
+pub struct Einsum<'py> {
+    subscripts: String,
+    operands: Vec<&'py PyAny>,
+    out: Option<&'py PyAny>,
+    casting: Casting,
+    order: NPY_ORDER,
+}
+
+impl<'py> Einsum<'py> {
+    /// Starts building a summation with the given subscripts, e.g. `"ij,jk->ik"`.
+    pub fn new(subscripts: &str) -> Self {
+        Self {
+            subscripts: subscripts.to_owned(),
+            operands: Vec::new(),
+            out: None,
+            casting: Casting::Safe,
+            order: NPY_ORDER::NPY_KEEPORDER,
+        }
+    }
+
+    /// Adds the next operand.
+    pub fn operand<T: Element, D: Dimension>(mut self, array: &'py PyArray<T, D>) -> Self {
+        self.operands.push(array);
+        self
+    }
+
+    /// Writes the result into `out` instead of allocating a new array.
+    pub fn out<T: Element, D: Dimension>(mut self, out: &'py PyArray<T, D>) -> Self {
+        self.out = Some(out);
+        self
+    }
+
+    /// Sets the casting rule for converting the operands to the element type of the result.
+    pub fn casting(mut self, casting: Casting) -> Self {
+        self.casting = casting;
+        self
+    }
+
+    /// Sets the memory layout of the result, defaults to `NPY_KEEPORDER`.
+    pub fn order(mut self, order: NPY_ORDER) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Computes the summation, returning `out` if it was given.
+    ///
+    /// Returns `ValueError` if the subscripts are malformed or do not match the number of operands.
+    pub fn call<T: Element, D: Dimension>(self) -> PyResult<&'py PyArray<T, D>> {
+        let py = match self.operands.first() {
+            Some(operand) => operand.py(),
+            None => {
+                return Err(PyValueError::new_err(
+                    "einsum requires at least one operand",
+                ))
+            }
+        };
+        let subscripts = check_subscripts(&self.subscripts, self.operands.len())?;
+        let mut operands: Vec<_> = self.operands.iter().map(|op| op.as_ptr()).collect();
+        let out = self.out.map_or(ptr::null_mut(), |out| out.as_ptr());
+        let obj = unsafe {
+            let result = PY_ARRAY_API.PyArray_EinsteinSum(
+                subscripts.as_ptr() as _,
+                operands.len() as _,
+                operands.as_mut_ptr() as _,
+                T::get_dtype(py).as_dtype_ptr(),
+                self.order,
+                self.casting.to_c_enum(),
+                out as _,
+            );
+            PyAny::from_owned_ptr_or_err(py, result)?
+        };
+        obj.extract()
+    }
+}
+
+fn check_subscripts(subscripts: &str, nop: usize) -> PyResult<CString> {
+    if let Some(c) = subscripts
+        .chars()
+        .find(|&c| !(c.is_ascii_alphabetic() || ",.->".contains(c) || c == ' '))
     {
-        Ok(subscripts) => subscripts.into(),
-        Err(_) => std::ffi::CString::new(subscripts).unwrap().into(),
-    };
-    let obj = unsafe {
-        let result = PY_ARRAY_API.PyArray_EinsteinSum(
-            subscripts.as_ptr() as _,
-            arrays.len() as _,
-            arrays.as_ptr() as _,
-            std::ptr::null_mut(),
-            NPY_ORDER::NPY_KEEPORDER,
-            NPY_CASTING::NPY_NO_CASTING,
-            std::ptr::null_mut(),
-        );
-        PyAny::from_owned_ptr_or_err(arrays[0].py(), result)?
-    };
-    obj.extract()
+        return Err(PyValueError::new_err(format!(
+            "invalid character {:?} in einsum subscripts {:?}",
+            c, subscripts
+        )));
+    }
+    let mut parts = subscripts.split("->");
+    let inputs = parts.next().unwrap_or_default();
+    if parts.count() > 1 {
+        return Err(PyValueError::new_err(format!(
+            "einsum subscripts {:?} contain more than one '->'",
+            subscripts
+        )));
+    }
+    let terms = inputs.split(',').count();
+    if terms != nop {
+        return Err(PyValueError::new_err(format!(
+            "einsum subscripts {:?} specify {} operands, but {} were given",
+            subscripts, terms, nop
+        )));
+    }
+    // Interior NUL characters were already rejected above.
+    Ok(CString::new(subscripts).unwrap())
 }
 
 /// Return the Einstein summation convention of given tensors.
@@ -105,15 +598,15 @@ where
 ///     let b = numpy::pyarray![py, [20, 30], [40, 50], [60, 70]];
 ///     let einsum = numpy::einsum!("ijk,ji->ik", a, b).unwrap();
 ///     assert_eq!(
-///         einsum.readonly().as_array(),
+///         einsum.readonly().unwrap().as_array(),
 ///         ndarray::array![[640,  760,  880, 1000], [2560, 2710, 2860, 3010]]
 ///     );
 /// });
 /// ```
 #[macro_export]
 macro_rules! einsum {
-    ($subscripts: literal $(,$array: ident)+ $(,)*) => {{
+    ($subscripts: literal $(,$array: expr)+ $(,)*) => {{
         let arrays = [$($array.to_dyn(),)+];
-        unsafe { $crate::einsum_impl(concat!($subscripts, "\0"), &arrays) }
+        $crate::einsum_impl(concat!($subscripts, "\0"), &arrays)
     }};
 }
@@ -0,0 +1,116 @@
+//! Support for arrays whose elements are not aligned as required by their Rust type
+use std::{fmt, mem, ptr};
+
+use ndarray::{Array, Dimension};
+use pyo3::Python;
+
+use crate::dtype::{Element, PyArrayDescr};
+use crate::PyArray;
+
+/// An element of type `T` which can be stored at any address, irrespective of the alignment of `T`,
+/// and which is read using unaligned loads.
+///
+/// Its data type is the one of `T`, so that every array of `T` can be viewed as an array of
+/// `Unaligned<T>` using [`PyArray::as_unaligned`], which can then be safely accessed via
+/// [`readonly`](crate::PyArray::readonly) even if the original array is not aligned.
+///
+/// # Example
+/// ```
+/// use numpy::{PyArray1, Unaligned};
+/// use pyo3::types::IntoPyDict;
+/// pyo3::Python::with_gil(|py| {
+///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+///     let array: &PyArray1<f64> = py
+///         .eval(
+///             "np.frombuffer(b'\\0' + np.array([1.5, 2.5]).tobytes(), dtype='float64', offset=1)",
+///             Some(locals),
+///             None,
+///         )
+///         .unwrap()
+///         .extract()
+///         .unwrap();
+///     assert!(!array.is_aligned());
+///     assert!(array.readonly().is_err());
+///
+///     let unaligned: &PyArray1<Unaligned<f64>> = array.as_unaligned();
+///     let values = unaligned.readonly().unwrap().as_array().map(|x| x.get());
+///     assert_eq!(values, ndarray::array![1.5, 2.5]);
+/// });
+/// ```
+#[repr(C, packed)]
+pub struct Unaligned<T>(T);
+
+impl<T: Copy> Unaligned<T> {
+    /// Stores `value` without any alignment requirement.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Reads the stored value using an unaligned load.
+    pub fn get(&self) -> T {
+        // `Unaligned<T>` is `repr(C)` with a single field, i.e. the value is stored at offset zero.
+        unsafe { ptr::read_unaligned(self as *const Self as *const T) }
+    }
+}
+
+impl<T: Copy> Clone for Unaligned<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy> Copy for Unaligned<T> {}
+
+impl<T: Copy> From<T> for Unaligned<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for Unaligned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Unaligned").field(&self.get()).finish()
+    }
+}
+
+unsafe impl<T: Element + Copy> Element for Unaligned<T> {
+    const IS_COPY: bool = true;
+
+    fn get_dtype(py: Python) -> &PyArrayDescr {
+        T::get_dtype(py)
+    }
+}
+
+impl<T: Element + Copy, D: Dimension> PyArray<T, D> {
+    /// Views this array as an array of [`Unaligned<T>`] which can be accessed safely
+    /// even if this array is not [aligned](#method.is_aligned).
+    pub fn as_unaligned(&self) -> &PyArray<Unaligned<T>, D> {
+        debug_assert_eq!(mem::size_of::<Unaligned<T>>(), mem::size_of::<T>());
+        // Both types are thin wrappers around the same Python object.
+        unsafe { &*(self as *const Self as *const PyArray<Unaligned<T>, D>) }
+    }
+
+    /// Get a copy of `PyArray` as [`ndarray::Array`] like [`to_owned_array`](#method.to_owned_array),
+    /// but using unaligned loads so that arrays which are not [aligned](#method.is_aligned)
+    /// are supported as well.
+    ///
+    /// # Example
+    /// ```
+    /// use numpy::PyArray1;
+    /// use pyo3::types::IntoPyDict;
+    /// pyo3::Python::with_gil(|py| {
+    ///     let locals = [("np", numpy::get_array_module(py).unwrap())].into_py_dict(py);
+    ///     let array: &PyArray1<i32> = py
+    ///         .eval("np.arange(5, dtype='int32').view('uint8')[1:-3].view('int32')", Some(locals), None)
+    ///         .unwrap()
+    ///         .extract()
+    ///         .unwrap();
+    ///     assert!(!array.is_aligned());
+    ///     assert_eq!(array.to_owned_array_unaligned().len(), 4);
+    /// });
+    /// ```
+    pub fn to_owned_array_unaligned(&self) -> Array<T, D> {
+        // `Unaligned<T>` has an alignment of one and the elements are copied immediately.
+        unsafe { self.as_unaligned().as_array() }.map(Unaligned::get)
+    }
+}
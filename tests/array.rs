@@ -83,14 +83,14 @@ fn arange() {
 fn as_array() {
     pyo3::Python::with_gil(|py| {
         let arr = PyArray::<f64, _>::zeros(py, [3, 2, 4], false);
-        let arr = arr.readonly();
+        let arr = arr.readonly().unwrap();
         let a = arr.as_array();
         assert_eq!(arr.shape(), a.shape());
         assert_eq!(
             arr.strides().iter().map(|x| x / 8).collect::<Vec<_>>(),
             a.strides()
         );
-        let not_contiguous = not_contiguous_array(py).readonly();
+        let not_contiguous = not_contiguous_array(py).readonly().unwrap();
         assert_eq!(not_contiguous.as_array(), array![1, 3]);
     })
 }
@@ -109,9 +109,11 @@ fn as_raw_array() {
 #[test]
 fn as_slice() {
     pyo3::Python::with_gil(|py| {
-        let arr = PyArray::<i32, _>::zeros(py, [3, 2, 4], false).readonly();
+        let arr = PyArray::<i32, _>::zeros(py, [3, 2, 4], false)
+            .readonly()
+            .unwrap();
         assert_eq!(arr.as_slice().unwrap().len(), 3 * 2 * 4);
-        let not_contiguous = not_contiguous_array(py).readonly();
+        let not_contiguous = not_contiguous_array(py).readonly().unwrap();
         assert!(not_contiguous.as_slice().is_err());
     })
 }
@@ -130,7 +132,10 @@ fn from_vec2() {
     let vec2 = vec![vec![1, 2, 3]; 2];
     pyo3::Python::with_gil(|py| {
         let pyarray = PyArray::from_vec2(py, &vec2).unwrap();
-        assert_eq!(pyarray.readonly().as_array(), array![[1, 2, 3], [1, 2, 3]]);
+        assert_eq!(
+            pyarray.readonly().unwrap().as_array(),
+            array![[1, 2, 3], [1, 2, 3]]
+        );
         assert!(PyArray::from_vec2(py, &[vec![1], vec![2, 3]]).is_err());
     })
 }
@@ -141,7 +146,7 @@ fn from_vec3() {
     pyo3::Python::with_gil(|py| {
         let pyarray = PyArray::from_vec3(py, &vec3).unwrap();
         assert_eq!(
-            pyarray.readonly().as_array(),
+            pyarray.readonly().unwrap().as_array(),
             array![[[1, 2], [1, 2]], [[1, 2], [1, 2]]]
         );
     })
@@ -156,7 +161,7 @@ fn from_eval_to_fixed() {
             .unwrap()
             .extract()
             .unwrap();
-        assert_eq!(pyarray.readonly().as_array(), array![1, 2, 3]);
+        assert_eq!(pyarray.readonly().unwrap().as_array(), array![1, 2, 3]);
     })
 }
 
@@ -174,7 +179,7 @@ fn from_eval_to_dyn() {
             .extract()
             .unwrap();
         assert_eq!(
-            pyarray.readonly().as_array(),
+            pyarray.readonly().unwrap().as_array(),
             array![[1, 2], [3, 4]].into_dyn()
         );
     })
@@ -194,7 +199,7 @@ fn from_eval_to_dyn_u64() {
             .extract()
             .unwrap();
         assert_eq!(
-            pyarray.readonly().as_array(),
+            pyarray.readonly().unwrap().as_array(),
             array![[1, 2], [3, 4]].into_dyn()
         );
     })
@@ -230,7 +235,10 @@ fn array_cast() {
     pyo3::Python::with_gil(|py| {
         let arr_f64 = PyArray::from_vec2(py, &vec2).unwrap();
         let arr_i32: &PyArray2<i32> = arr_f64.cast(false).unwrap();
-        assert_eq!(arr_i32.readonly().as_array(), array![[1, 2, 3], [1, 2, 3]]);
+        assert_eq!(
+            arr_i32.readonly().unwrap().as_array(),
+            array![[1, 2, 3], [1, 2, 3]]
+        );
     })
 }
 
@@ -263,6 +271,74 @@ fn dtype_from_py() {
     })
 }
 
+#[test]
+fn dtype_builders() {
+    pyo3::Python::with_gil(|py| {
+        let float64 = numpy::dtype::<f64>(py);
+        let point = PyArrayDescr::structured(
+            py,
+            &[
+                ("x", float64, 0),
+                ("y", float64, 8),
+                ("tag", numpy::dtype::<u8>(py), 16),
+            ],
+            24,
+            false,
+        )
+        .unwrap();
+        assert_eq!(point.names(), Some(vec!["x", "y", "tag"]));
+        assert_eq!(point.itemsize(), 24);
+        assert!(!point.is_aligned_struct());
+
+        // Misaligned offsets are rejected for aligned structs.
+        assert!(PyArrayDescr::structured(
+            py,
+            &[("a", numpy::dtype::<u8>(py), 0), ("b", float64, 1)],
+            9,
+            true
+        )
+        .is_err());
+
+        let matrix = PyArrayDescr::subarray(float64, &[3, 3]).unwrap();
+        assert_eq!(matrix.ndim(), 2);
+        assert_eq!(matrix.itemsize(), 72);
+
+        let swapped = float64.with_byteorder(ByteOrder::Swap).unwrap();
+        assert_eq!(swapped.is_native_byteorder(), Some(false));
+        assert!(!swapped.is_equiv_to(float64));
+        assert!(swapped
+            .with_byteorder(ByteOrder::Native)
+            .unwrap()
+            .is_equiv_to(float64));
+
+        let datetime = PyArrayDescr::datetime(py, DatetimeUnit::Nanoseconds).unwrap();
+        assert_eq!(datetime.kind(), b'M');
+        assert!(datetime.is_equiv_to(PyArrayDescr::new(py, "datetime64[ns]").unwrap()));
+        let timedelta = PyArrayDescr::timedelta(py, DatetimeUnit::Days).unwrap();
+        assert_eq!(timedelta.kind(), b'm');
+
+        let string = PyArrayDescr::string(py, 5).unwrap();
+        assert_eq!((string.kind(), string.itemsize()), (b'S', 5));
+        let unicode = PyArrayDescr::unicode(py, 5).unwrap();
+        assert_eq!((unicode.kind(), unicode.itemsize()), (b'U', 20));
+    })
+}
+
+#[test]
+fn dtype_can_cast_to() {
+    pyo3::Python::with_gil(|py| {
+        let int32 = numpy::dtype::<i32>(py);
+        let float32 = numpy::dtype::<f32>(py);
+        let float64 = numpy::dtype::<f64>(py);
+        assert!(int32.can_cast_to(float64, Casting::Safe));
+        assert!(!int32.can_cast_to(float32, Casting::Safe));
+        assert!(float64.can_cast_to(float32, Casting::SameKind));
+        assert!(!float64.can_cast_to(int32, Casting::SameKind));
+        assert!(float64.can_cast_to(int32, Casting::Unsafe));
+        assert!(!int32.can_cast_to(float64, Casting::No));
+    })
+}
+
 #[test]
 fn borrow_from_array() {
     use numpy::ndarray::Array1;
@@ -326,3 +402,523 @@ fn downcasting_respects_dimensionality() {
         assert!(ob.downcast::<PyArray2<i32>>().is_err());
     })
 }
+
+#[test]
+fn from_buffer_shares_memory() {
+    Python::with_gil(|py| {
+        let buf = py.eval("bytearray(b'abcd')", None, None).unwrap();
+        let array = PyArray1::<u8>::from_buffer(buf).unwrap();
+        assert_eq!(array.readonly().unwrap().as_slice().unwrap(), b"abcd");
+
+        pyo3::py_run!(py, array buf, "array[0] = ord('x'); assert buf == bytearray(b'xbcd')");
+    })
+}
+
+#[test]
+fn from_buffer_multidimensional() {
+    Python::with_gil(|py| {
+        let view = py
+            .eval(
+                "memoryview(__import__('array').array('i', range(6))).cast('B').cast('i', (2, 3))",
+                None,
+                None,
+            )
+            .unwrap();
+        let array = PyArray2::<i32>::from_buffer(view).unwrap();
+        assert_eq!(
+            array.readonly().unwrap().as_array(),
+            array![[0, 1, 2], [3, 4, 5]]
+        );
+
+        let readonly = py.eval("b'ab'", None, None).unwrap();
+        let array = PyArray1::<u8>::from_buffer(readonly).unwrap();
+        pyo3::py_run!(py, array, "assert not array.flags['WRITEABLE']");
+    })
+}
+
+#[test]
+fn from_buffer_checks_format_and_dimensionality() {
+    Python::with_gil(|py| {
+        let buf = py
+            .eval("__import__('array').array('h', [1, 2])", None, None)
+            .unwrap();
+        assert!(PyArray1::<i16>::from_buffer(buf).is_ok());
+        assert!(PyArray1::<u16>::from_buffer(buf).is_err());
+        assert!(PyArray2::<i16>::from_buffer(buf).is_err());
+
+        let unaligned = py
+            .eval(
+                "memoryview(__import__('array').array('h', [1, 2, 3]))[1:].cast('B')[1:3].cast('h')",
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(PyArray1::<i16>::from_buffer(unaligned).is_err());
+    })
+}
+
+#[test]
+fn as_buffer() {
+    Python::with_gil(|py| {
+        let array = PyArray::from_slice(py, &[1.0_f64, 2.0, 3.0]);
+        let buffer = array.as_buffer().unwrap();
+        assert_eq!(buffer.dimensions(), 1);
+        assert_eq!(buffer.to_vec(py).unwrap(), [1.0, 2.0, 3.0]);
+    })
+}
+
+#[test]
+fn array_like_from_array_interface() {
+    Python::with_gil(|py| {
+        let locals = get_np_locals(py);
+        py.run(
+            r#"
+class Interface:
+    def __init__(self, base):
+        self.base = base
+        self.__array_interface__ = base.__array_interface__
+
+obj = Interface(np.arange(6, dtype='int64').reshape(2, 3))
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let obj = locals.get_item("obj").unwrap();
+        let array: PyArrayLike2<i64> = obj.extract().unwrap();
+        assert_eq!(
+            array.readonly().unwrap().as_array(),
+            array![[0, 1, 2], [3, 4, 5]]
+        );
+
+        let err = obj.extract::<PyArrayLike2<f64>>().err().unwrap();
+        assert!(err.to_string().contains("typestr <i8"));
+        assert!(obj.extract::<PyArrayLike1<i64>>().is_err());
+    })
+}
+
+#[test]
+fn array_like_from_array_attr() {
+    Python::with_gil(|py| {
+        let locals = get_np_locals(py);
+        let obj = py
+            .eval(
+                "type('Wrapper', (), {'__array__': lambda self, dtype=None: np.ones(2, dtype='int32')})()",
+                Some(locals),
+                None,
+            )
+            .unwrap();
+        let array: PyArrayLike1<i32> = obj.extract().unwrap();
+        assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[1, 1]);
+    })
+}
+
+#[test]
+fn array_like_rejects_other_objects() {
+    Python::with_gil(|py| {
+        let obj = py.eval("[1, 2, 3]", None, None).unwrap();
+        assert!(obj.extract::<PyArrayLike1<i64>>().is_err());
+
+        let locals = get_np_locals(py);
+        let obj = py
+            .eval(
+                "type('Broken', (), {'__array_interface__': {'shape': (2,), 'typestr': '<q'}})()",
+                Some(locals),
+                None,
+            )
+            .unwrap();
+        assert!(obj.extract::<PyArrayLike1<i64>>().is_err());
+
+        py.run(
+            r#"
+base = np.frombuffer(b'\0' * 17, dtype='<i8', offset=1)
+unaligned = type('Unaligned', (), {'__array_interface__': base.__array_interface__})()
+"#,
+            Some(locals),
+            None,
+        )
+        .unwrap();
+        let obj = locals.get_item("unaligned").unwrap();
+        let err = obj.extract::<PyArrayLike1<i64>>().err().unwrap();
+        assert!(err.to_string().contains("not aligned"));
+    })
+}
+
+#[derive(Clone)]
+#[repr(C)]
+struct Particle {
+    id: i64,
+    mass: f64,
+}
+
+unsafe impl Element for Particle {
+    const IS_COPY: bool = true;
+
+    fn get_dtype(py: Python) -> &PyArrayDescr {
+        PyArrayDescr::new(py, &vec![("id", "i8"), ("mass", "f8")]).unwrap()
+    }
+}
+
+#[test]
+fn record_field() {
+    pyo3::Python::with_gil(|py| {
+        let particles = PyArray1::from_vec(
+            py,
+            vec![
+                Particle { id: 1, mass: 0.5 },
+                Particle { id: 2, mass: 1.5 },
+                Particle { id: 3, mass: 2.5 },
+            ],
+        );
+        let ids = particles.field::<i64>("id").unwrap();
+        assert_eq!(ids.readonly().unwrap().as_array(), array![1, 2, 3]);
+        let masses: &PyArray1<f64> = particles.field("mass").unwrap();
+        assert_eq!(masses.strides(), &[16]);
+
+        // The field shares its data with the record array.
+        unsafe { masses.as_array_mut()[1] = 42.0 };
+        assert_eq!(particles.readonly().unwrap().as_array()[1].mass, 42.0);
+
+        particles
+            .set_field("id", PyArray1::from_slice(py, &[7_i64]))
+            .unwrap();
+        assert_eq!(ids.readonly().unwrap().as_array(), array![7, 7, 7]);
+
+        assert!(particles.field::<f64>("charge").is_err());
+        assert!(particles.field::<f32>("mass").is_err());
+        assert!(particles
+            .set_field("mass", PyArray1::from_slice(py, &[1_i64]))
+            .is_err());
+        assert!(PyArray1::<f64>::zeros(py, 3, false)
+            .field::<f64>("mass")
+            .is_err());
+    })
+}
+
+#[test]
+fn non_native_byteorder() {
+    pyo3::Python::with_gil(|py| {
+        let non_native = if cfg!(target_endian = "little") {
+            ">i4"
+        } else {
+            "<i4"
+        };
+        let locals = [("np", get_array_module(py).unwrap())].into_py_dict(py);
+        let obj = py
+            .eval(
+                &format!("np.arange(4, dtype='{}')", non_native),
+                Some(locals),
+                None,
+            )
+            .unwrap();
+
+        assert!(obj.extract::<&PyArray1<i32>>().is_err());
+        assert!(PyArray1::<i64>::from_any_byteorder(obj).is_err());
+
+        let native = PyArray1::<i32>::from_any_byteorder(obj).unwrap();
+        assert_eq!(
+            native.readonly().unwrap().as_slice().unwrap(),
+            &[0, 1, 2, 3]
+        );
+        assert_eq!(native.dtype().is_native_byteorder(), Some(true));
+
+        let swapped: &PyArray1<NonNative<i32>> = obj.extract().unwrap();
+        let values = swapped.to_vec().unwrap();
+        assert_eq!(
+            values.iter().map(|x| x.get()).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        unsafe { swapped.as_array_mut()[0].set(42) };
+        pyo3::py_run!(py, obj, "assert obj[0] == 42");
+
+        let copy = native.byteswap(false).unwrap();
+        assert_eq!(
+            copy.readonly().unwrap().as_slice().unwrap()[3],
+            3_i32.swap_bytes()
+        );
+        assert_eq!(native.readonly().unwrap().as_slice().unwrap()[3], 3);
+        native.byteswap(true).unwrap();
+        assert_eq!(
+            native.readonly().unwrap().as_slice().unwrap()[1],
+            1_i32.swap_bytes()
+        );
+    })
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+struct PackedRecord {
+    tag: u8,
+    value: f64,
+}
+
+unsafe impl Element for PackedRecord {
+    const IS_COPY: bool = true;
+
+    fn get_dtype(py: Python) -> &PyArrayDescr {
+        PyArrayDescr::new(py, &vec![("tag", "u1"), ("value", "f8")]).unwrap()
+    }
+}
+
+#[test]
+fn unaligned_packed_field() {
+    pyo3::Python::with_gil(|py| {
+        let records = PyArray1::from_vec(
+            py,
+            vec![
+                PackedRecord { tag: 1, value: 0.5 },
+                PackedRecord { tag: 2, value: 1.5 },
+                PackedRecord { tag: 3, value: 2.5 },
+            ],
+        );
+        let values = records.field::<f64>("value").unwrap();
+
+        assert!(!values.is_aligned());
+        assert!(values.readonly().is_err());
+        assert!(values
+            .to_object(py)
+            .extract::<PyReadonlyArray1<f64>>(py)
+            .is_err());
+
+        assert_eq!(values.to_owned_array_unaligned(), array![0.5, 1.5, 2.5]);
+        assert_eq!(values.to_owned_array(), array![0.5, 1.5, 2.5]);
+        let unaligned = values.as_unaligned().readonly().unwrap();
+        assert_eq!(unaligned.get([1]).unwrap().get(), 1.5);
+    })
+}
+
+#[test]
+fn unaligned_views_return_errors() {
+    pyo3::Python::with_gil(|py| {
+        let records = PyArray1::from_vec(py, vec![PackedRecord { tag: 1, value: 0.5 }]);
+        let values = records.field::<f64>("value").unwrap();
+        assert!(values.is_contiguous());
+
+        let err = unsafe { values.as_slice() }.unwrap_err();
+        assert!(err.to_string().contains("not aligned"));
+        assert!(values.to_vec().is_err());
+        assert_eq!(values.to_owned_array(), array![0.5]);
+        assert!(NpySingleIterBuilder::readwrite(values).build().is_err());
+
+        let err = values.readonly().err().unwrap();
+        assert!(err.to_string().contains("not aligned"));
+    })
+}
+
+#[pyclass]
+struct Payload {
+    value: i32,
+}
+
+#[test]
+fn downcast_object_elements() {
+    pyo3::Python::with_gil(|py| {
+        let array =
+            PyArray::from_pyclass_array(py, array![[Payload { value: 1 }, Payload { value: 2 }]])
+                .unwrap();
+        assert_eq!(array.shape(), &[1, 2]);
+
+        let readonly = array.readonly().unwrap();
+        let elements = readonly.downcast_elements::<Payload>().unwrap();
+        assert_eq!(elements[[0, 1]].borrow(py).value, 2);
+
+        let mut reordered = Array::from_shape_fn((2, 3), |(i, j)| Payload {
+            value: (10 * i + j) as i32,
+        });
+        reordered.invert_axis(Axis(1));
+        let reordered = reordered.reversed_axes();
+        let array = PyArray::from_pyclass_array(py, reordered).unwrap();
+        let readonly = array.readonly().unwrap();
+        let values = readonly
+            .downcast_elements::<Payload>()
+            .unwrap()
+            .map(|element| element.borrow(py).value);
+        assert_eq!(values, array![[2, 12], [1, 11], [0, 10]]);
+
+        let mixed = PyArray::from_owned_object_array(
+            py,
+            array![
+                Py::new(py, Payload { value: 3 }).unwrap().into_py(py),
+                py.None()
+            ],
+        );
+        assert!(mixed
+            .readonly()
+            .unwrap()
+            .downcast_elements::<Payload>()
+            .is_err());
+    })
+}
+
+#[test]
+fn string_object_arrays() {
+    pyo3::Python::with_gil(|py| {
+        let strings = PyArray::from_strings(py, &array![["a", "b"], ["c", "d"]]);
+        pyo3::py_run!(
+            py,
+            strings,
+            "assert strings.dtype == object and strings[1, 0] == 'c'"
+        );
+        assert_eq!(
+            strings.to_string_array().unwrap(),
+            array![["a", "b"], ["c", "d"]]
+        );
+
+        let locals = get_np_locals(py);
+        let optional: &PyArray1<PyObject> = py
+            .eval(
+                "np.array(['x', None, 'z'], dtype=object)",
+                Some(locals),
+                None,
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(
+            optional.to_optional_string_array().unwrap(),
+            array![Some("x".to_owned()), None, Some("z".to_owned())]
+        );
+        assert!(optional.to_string_array().is_err());
+
+        let mixed: &PyArray1<PyObject> = py
+            .eval("np.array(['x', 1], dtype=object)", Some(locals), None)
+            .unwrap()
+            .extract()
+            .unwrap();
+        let err = mixed.to_optional_string_array().unwrap_err();
+        assert!(err.to_string().contains("int"));
+    })
+}
+
+#[test]
+fn scalar_roundtrip() {
+    pyo3::Python::with_gil(|py| {
+        let locals = get_np_locals(py);
+        let scalar: PyScalar<f32> = py
+            .eval("np.float32(0.25)", Some(locals), None)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(scalar, PyScalar(0.25));
+
+        for code in &[
+            "np.float64(0.25)",
+            "0.25",
+            "np.array(0.25, dtype='float32')",
+        ] {
+            let ob = py.eval(code, Some(locals), None).unwrap();
+            assert!(ob.extract::<PyScalar<f32>>().is_err(), "{}", code);
+        }
+
+        let int = PyScalar(7_i16).into_py(py);
+        pyo3::py_run!(
+            py,
+            int,
+            "import numpy as np; assert type(int) is np.int16 and int == 7"
+        );
+        assert_eq!(int.extract::<PyScalar<i16>>(py).unwrap().0, 7);
+        let int = PyScalar(7_i16).to_scalar(py).unwrap();
+        assert_eq!(int.extract::<PyScalar<i16>>().unwrap().0, 7);
+
+        let complex = PyScalar(Complex64::new(1.0, -1.0)).into_py(py);
+        pyo3::py_run!(
+            py,
+            complex,
+            "assert complex.dtype == 'complex128' and complex == 1 - 1j"
+        );
+    })
+}
+
+#[test]
+fn structured_scalar_roundtrip() {
+    pyo3::Python::with_gil(|py| {
+        let record = PyScalar(PackedRecord { tag: 7, value: 2.5 }).into_py(py);
+        pyo3::py_run!(
+            py,
+            record,
+            "assert record['tag'] == 7 and record['value'] == 2.5"
+        );
+        let PyScalar(record) = record.extract::<PyScalar<PackedRecord>>(py).unwrap();
+        let (tag, value) = (record.tag, record.value);
+        assert_eq!((tag, value), (7, 2.5));
+
+        let other = py
+            .eval(
+                "np.zeros(1, dtype=[('tag', 'u1'), ('value', 'f4')])[0]",
+                Some(get_np_locals(py)),
+                None,
+            )
+            .unwrap();
+        assert!(other.extract::<PyScalar<PackedRecord>>().is_err());
+    })
+}
+
+#[test]
+fn masked_array() {
+    pyo3::Python::with_gil(|py| {
+        let locals = get_np_locals(py);
+        let masked: PyMaskedArray2<i32> = py
+            .eval(
+                "np.ma.masked_equal([[1, -1], [3, 4]], -1)",
+                Some(locals),
+                None,
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+        let (data, mask) = masked.as_array_with_mask();
+        assert_eq!(data, array![[1, -1], [3, 4]]);
+        assert_eq!(mask, array![[false, true], [false, false]]);
+        assert_eq!(
+            masked.iter().collect::<Vec<_>>(),
+            [Some(&1), None, Some(&3), Some(&4)]
+        );
+
+        let unmasked: PyMaskedArray1<f64> = py
+            .eval("np.ma.MaskedArray([1.0, 2.0])", Some(locals), None)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(unmasked.mask().as_array(), array![false, false]);
+
+        let plain = py.eval("np.arange(2.0)", Some(locals), None).unwrap();
+        assert!(plain.extract::<PyMaskedArray1<f64>>().is_err());
+
+        let data = PyArray::from_slice(py, &[1.0, 2.0, 3.0]);
+        let mask = PyArray::from_slice(py, &[true, false, false]);
+        let masked = PyMaskedArray::new(data, mask).unwrap();
+        assert_eq!(masked.iter().flatten().sum::<f64>(), 5.0);
+        let masked = masked.into_py(py);
+        pyo3::py_run!(py, masked, "assert masked.mean() == 2.5");
+
+        let short_mask = PyArray::from_slice(py, &[true, false]);
+        assert!(PyMaskedArray::new(data, short_mask).is_err());
+    })
+}
+
+#[test]
+fn ndarray_subclasses() {
+    pyo3::Python::with_gil(|py| {
+        let locals = get_np_locals(py);
+        let masked = py
+            .eval("np.ma.masked_less([1, 2, 3], 2)", Some(locals), None)
+            .unwrap();
+        let array: &PyArray1<i64> = masked.extract().unwrap();
+        assert!(!array.is_exact_ndarray());
+        assert!(PyArray1::<i64>::extract_exact(masked).is_err());
+
+        let plain = py.eval("np.arange(3)", Some(locals), None).unwrap();
+        let plain = PyArray1::<i64>::extract_exact(plain).unwrap();
+        assert!(plain.is_exact_ndarray());
+
+        let result = PyArray1::from_slice(py, &[4_i64, 5, 6]);
+        let wrapped = result.wrap_like(masked).unwrap();
+        pyo3::py_run!(
+            py,
+            wrapped,
+            "import numpy as np; assert isinstance(wrapped, np.ma.MaskedArray) and wrapped.sum() == 15"
+        );
+        let unwrapped = result.wrap_like(plain).unwrap();
+        pyo3::py_run!(py, unwrapped result, "assert unwrapped is result");
+    })
+}
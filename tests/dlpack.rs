@@ -0,0 +1,111 @@
+use ndarray::*;
+use numpy::{dlpack::DLDataType, PyArray, PyArray1, PyArray2};
+use pyo3::{prelude::*, types::PyDict};
+
+/// Wraps `capsule` into an object implementing the `__dlpack__` protocol.
+fn producer<'py>(py: Python<'py>, capsule: PyObject) -> &'py PyAny {
+    let locals = PyDict::new(py);
+    locals.set_item("capsule", capsule).unwrap();
+    py.run(
+        r#"
+class Producer:
+    def __dlpack__(self, stream=None):
+        return capsule
+
+    def __dlpack_device__(self):
+        return (1, 0)
+
+producer = Producer()
+"#,
+        None,
+        Some(locals),
+    )
+    .unwrap();
+    locals.get_item("producer").unwrap()
+}
+
+#[test]
+fn round_trip() {
+    Python::with_gil(|py| {
+        let array = PyArray::arange(py, 0.0, 6.0, 1.0).reshape([2, 3]).unwrap();
+        let capsule = array.to_dlpack().unwrap();
+        let imported = PyArray2::<f64>::from_dlpack(producer(py, capsule)).unwrap();
+        assert_eq!(
+            imported.readonly().unwrap().as_array(),
+            array![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]
+        );
+
+        // Both arrays share their data, but legacy capsules are imported as readonly.
+        unsafe { *array.uget_mut([1, 1]) = 42.0 };
+        assert_eq!(imported.readonly().unwrap().as_array()[[1, 1]], 42.0);
+        pyo3::py_run!(py, imported, "assert not imported.flags['WRITEABLE']");
+    })
+}
+
+#[test]
+fn round_trip_strided() {
+    Python::with_gil(|py| {
+        let array = PyArray::arange(py, 0_i64, 12, 1).reshape([3, 4]).unwrap();
+        let locals = PyDict::new(py);
+        locals.set_item("array", array).unwrap();
+        let view: &PyArray2<i64> = py
+            .eval("array[::2, ::-3]", None, Some(locals))
+            .unwrap()
+            .downcast()
+            .unwrap();
+
+        let capsule = view.to_dlpack().unwrap();
+        let imported = PyArray2::<i64>::from_dlpack(producer(py, capsule)).unwrap();
+        assert_eq!(
+            imported.readonly().unwrap().as_array(),
+            array![[3, 0], [11, 8]]
+        );
+    })
+}
+
+#[test]
+fn capsule_can_only_be_consumed_once() {
+    Python::with_gil(|py| {
+        let array = PyArray1::from_slice(py, &[1_u8, 2, 3]);
+        let capsule = array.to_dlpack().unwrap();
+        let producer = producer(py, capsule);
+        assert!(PyArray1::<u8>::from_dlpack(producer).is_ok());
+        assert!(PyArray1::<u8>::from_dlpack(producer).is_err());
+    })
+}
+
+#[test]
+fn checks_type_and_dimensionality() {
+    Python::with_gil(|py| {
+        let array = PyArray1::from_slice(py, &[1_i32, 2, 3]);
+        let capsule = array.to_dlpack().unwrap();
+        assert!(PyArray1::<u32>::from_dlpack(producer(py, capsule)).is_err());
+
+        let capsule = array.to_dlpack().unwrap();
+        assert!(PyArray2::<i32>::from_dlpack(producer(py, capsule)).is_err());
+    })
+}
+
+#[test]
+fn readonly_arrays_are_not_exported() {
+    Python::with_gil(|py| {
+        let array = PyArray1::from_slice(py, &[1.0_f64]);
+        let readonly = array.readonly().unwrap();
+        assert!(array.to_dlpack().is_err());
+        drop(readonly);
+        assert!(array.to_dlpack().is_ok());
+    })
+}
+
+#[test]
+fn data_types() {
+    Python::with_gil(|py| {
+        let dtype = DLDataType::of::<bool>(py).unwrap();
+        assert_eq!((dtype.code, dtype.bits), (DLDataType::KDLBOOL, 8));
+        let dtype = DLDataType::of::<u16>(py).unwrap();
+        assert_eq!((dtype.code, dtype.bits), (DLDataType::KDLUINT, 16));
+        let dtype = DLDataType::of::<numpy::Complex64>(py).unwrap();
+        assert_eq!((dtype.code, dtype.bits), (DLDataType::KDLCOMPLEX, 128));
+        assert!(DLDataType::of::<PyObject>(py).is_err());
+    })
+}
@@ -0,0 +1,229 @@
+use ndarray::*;
+use numpy::{get_array_module, io, Element, PyArray, PyArray1, PyArray2, PyArrayDescr, PyArrayDyn};
+use pyo3::{
+    prelude::*,
+    types::{IntoPyDict, PyBytes, PyDict},
+};
+
+fn get_np_locals(py: Python) -> &PyDict {
+    [("np", get_array_module(py).unwrap())].into_py_dict(py)
+}
+
+/// Serializes the result of `expr` using `np.save`.
+fn np_save(py: Python, expr: &str) -> Vec<u8> {
+    let locals = get_np_locals(py);
+    py.run(
+        &format!("import io\nbuf = io.BytesIO()\nnp.save(buf, {})", expr),
+        None,
+        Some(locals),
+    )
+    .unwrap();
+    let buf = locals.get_item("buf").unwrap();
+    let bytes: &PyBytes = buf.call_method0("getvalue").unwrap().downcast().unwrap();
+    bytes.as_bytes().to_vec()
+}
+
+/// Deserializes `data` using `np.load`.
+fn np_load<'py>(py: Python<'py>, data: &[u8]) -> &'py PyAny {
+    let locals = get_np_locals(py);
+    locals.set_item("data", PyBytes::new(py, data)).unwrap();
+    py.eval(
+        "np.load(__import__('io').BytesIO(data))",
+        None,
+        Some(locals),
+    )
+    .unwrap()
+}
+
+#[test]
+fn read_written_by_numpy() {
+    pyo3::Python::with_gil(|py| {
+        let data = np_save(py, "np.arange(6, dtype='float64').reshape(2, 3)");
+        let array: &PyArray2<f64> = io::read_npy(py, &data[..]).unwrap();
+        assert_eq!(
+            array.readonly().unwrap().as_array(),
+            array![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]
+        );
+    })
+}
+
+#[test]
+fn read_fortran_order() {
+    pyo3::Python::with_gil(|py| {
+        let data = np_save(
+            py,
+            "np.asfortranarray(np.arange(6, dtype='int32').reshape(2, 3))",
+        );
+        let array: Array2<i32> = io::read_npy_array(py, &data[..]).unwrap();
+        assert_eq!(array, array![[0, 1, 2], [3, 4, 5]]);
+    })
+}
+
+#[test]
+fn read_non_native_byte_order() {
+    pyo3::Python::with_gil(|py| {
+        let data = np_save(py, "np.arange(3, dtype='>i8')");
+        let array: &PyArray1<i64> = io::read_npy(py, &data[..]).unwrap();
+        assert_eq!(array.readonly().unwrap().as_slice().unwrap(), &[0, 1, 2]);
+    })
+}
+
+#[test]
+fn read_checks_type_and_dimensionality() {
+    pyo3::Python::with_gil(|py| {
+        let data = np_save(py, "np.zeros((2, 2), dtype='float32')");
+        assert!(io::read_npy::<f64, Ix2, _>(py, &data[..]).is_err());
+        assert!(io::read_npy::<f32, Ix1, _>(py, &data[..]).is_err());
+        assert!(io::read_npy::<f32, IxDyn, _>(py, &data[..]).is_ok());
+    })
+}
+
+#[test]
+fn read_rejects_invalid_files() {
+    pyo3::Python::with_gil(|py| {
+        assert!(io::read_npy::<f64, Ix1, _>(py, &b"NUMPY"[..]).is_err());
+
+        let mut data = np_save(py, "np.zeros(4)");
+        data.truncate(data.len() - 1);
+        assert!(io::read_npy::<f64, Ix1, _>(py, &data[..]).is_err());
+
+        let huge_header = b"\x93NUMPY\x02\x00\xff\xff\xff\xff";
+        assert!(io::read_npy::<f64, Ix1, _>(py, &huge_header[..]).is_err());
+    })
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct LongName(f64);
+
+unsafe impl Element for LongName {
+    const IS_COPY: bool = true;
+
+    fn get_dtype(py: Python) -> &PyArrayDescr {
+        PyArrayDescr::new(py, &vec![("x".repeat(70_000), "f8")]).unwrap()
+    }
+}
+
+#[test]
+fn large_header_round_trip() {
+    pyo3::Python::with_gil(|py| {
+        let array = PyArray::from_vec(py, vec![LongName(0.5), LongName(1.5)]);
+        let mut data = Vec::new();
+        io::write_npy(&mut data, array).unwrap();
+        // Headers longer than 64 KiB require version 2.0.
+        assert_eq!(&data[6..8], b"\x02\x00");
+
+        let read: &PyArray1<LongName> = io::read_npy(py, &data[..]).unwrap();
+        let values = read.readonly().unwrap().as_array().map(|x| x.0);
+        assert_eq!(values, array![0.5, 1.5]);
+    })
+}
+
+#[test]
+fn write_read_by_numpy() {
+    pyo3::Python::with_gil(|py| {
+        let array = PyArray::from_vec2(py, &[vec![1i32, 2, 3], vec![4, 5, 6]]).unwrap();
+        let mut data = Vec::new();
+        io::write_npy(&mut data, array).unwrap();
+        // The header is padded such that the data is aligned to 64 bytes.
+        assert_eq!((data.len() - 6 * 4) % 64, 0);
+
+        let loaded = np_load(py, &data);
+        let locals = get_np_locals(py);
+        locals.set_item("loaded", loaded).unwrap();
+        py.run(
+            "assert (loaded == np.array([[1, 2, 3], [4, 5, 6]])).all()",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+    })
+}
+
+#[test]
+fn write_ndarray_round_trip() {
+    pyo3::Python::with_gil(|py| {
+        let array = Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as u16);
+        let layouts = [
+            array.clone(),
+            array.clone().reversed_axes(),
+            array.slice(s![.., ..;2, ..]).to_owned(),
+        ];
+        for array in layouts.iter() {
+            let mut data = Vec::new();
+            io::write_npy_array(py, &mut data, array).unwrap();
+            let read: Array3<u16> = io::read_npy_array(py, &data[..]).unwrap();
+            assert_eq!(&read, array);
+
+            let view = array.slice(s![.., 1.., ..;-2]);
+            let mut data = Vec::new();
+            io::write_npy_array(py, &mut data, &view).unwrap();
+            let read: Array3<u16> = io::read_npy_array(py, &data[..]).unwrap();
+            assert_eq!(read, view);
+        }
+    })
+}
+
+#[test]
+fn write_non_contiguous_pyarray() {
+    pyo3::Python::with_gil(|py| {
+        let array: &PyArrayDyn<f32> = py
+            .eval(
+                "np.arange(12, dtype='float32').reshape(3, 4)[:, ::2]",
+                Some(get_np_locals(py)),
+                None,
+            )
+            .unwrap()
+            .downcast()
+            .unwrap();
+        let mut data = Vec::new();
+        io::write_npy(&mut data, array).unwrap();
+        let read: ArrayD<f32> = io::read_npy_array(py, &data[..]).unwrap();
+        assert_eq!(read, array.to_owned_array());
+    })
+}
+
+#[test]
+fn write_zero_dimensional() {
+    pyo3::Python::with_gil(|py| {
+        let array = arr0(42.0f64);
+        let mut data = Vec::new();
+        io::write_npy_array(py, &mut data, &array).unwrap();
+        assert!(data.windows(11).any(|w| w == b"'shape': ()"));
+        let read: Array0<f64> = io::read_npy_array(py, &data[..]).unwrap();
+        assert_eq!(read, array);
+    })
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz_round_trip() {
+    use numpy::io::npz::{NpzReader, NpzWriter};
+    use std::io::Cursor;
+
+    pyo3::Python::with_gil(|py| {
+        for &compressed in &[false, true] {
+            let mut writer = if compressed {
+                NpzWriter::new_compressed(Cursor::new(Vec::new()))
+            } else {
+                NpzWriter::new(Cursor::new(Vec::new()))
+            };
+            writer
+                .add_array("a", PyArray1::from_slice(py, &[1.0, 2.0]))
+                .unwrap();
+            writer
+                .add_array("b", PyArray::from_vec2(py, &[vec![1u8], vec![2]]).unwrap())
+                .unwrap();
+            let file = writer.finish().unwrap();
+
+            let mut reader = NpzReader::new(file).unwrap();
+            assert_eq!(reader.len(), 2);
+            assert_eq!(reader.names(), ["a", "b"]);
+            let a: &PyArray1<f64> = reader.by_name(py, "a").unwrap();
+            assert_eq!(a.readonly().unwrap().as_slice().unwrap(), &[1.0, 2.0]);
+            let b: &PyArray2<u8> = reader.by_name(py, "b.npy").unwrap();
+            assert_eq!(b.readonly().unwrap().as_array(), array![[1], [2]]);
+            assert!(reader.by_name::<f64, Ix1>(py, "c").is_err());
+        }
+    })
+}
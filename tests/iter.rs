@@ -1,6 +1,9 @@
 use ndarray::array;
-use numpy::{NpyMultiIterBuilder, NpySingleIterBuilder, PyArray};
-use pyo3::PyResult;
+use numpy::{
+    Casting, Complex32, IxDyn, NpyIterFlag, NpyMultiIterBuilder, NpySingleIterBuilder, PyArray,
+    PyArray1, PyArray2,
+};
+use pyo3::{types::IntoPyDict, PyResult};
 
 macro_rules! assert_approx_eq {
     ($x: expr, $y: expr) => {
@@ -14,7 +17,7 @@ fn readonly_iter() -> PyResult<()> {
         let data = array![[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]];
 
         let arr = PyArray::from_array(py, &data);
-        let iter = NpySingleIterBuilder::readonly(arr.readonly()).build()?;
+        let iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap()).build()?;
 
         // The order of iteration is not specified, so we should restrict ourselves
         // to tests that don't verify a given order.
@@ -32,7 +35,7 @@ fn mutable_iter() -> PyResult<()> {
         for elem in iter {
             *elem *= 2.0;
         }
-        let iter = NpySingleIterBuilder::readonly(arr.readonly()).build()?;
+        let iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap()).build()?;
         assert_approx_eq!(iter.sum::<f64>(), 30.0);
         Ok(())
     })
@@ -47,8 +50,8 @@ fn multiiter_rr() -> PyResult<()> {
         let arr1 = PyArray::from_array(py, &data1);
         let arr2 = PyArray::from_array(py, &data2);
         let iter = NpyMultiIterBuilder::new()
-            .add_readonly(arr1.readonly())
-            .add_readonly(arr2.readonly())
+            .add_readonly(arr1.readonly().unwrap())
+            .add_readonly(arr2.readonly().unwrap())
             .build()
             .map_err(|e| e.print(py))
             .unwrap();
@@ -72,7 +75,7 @@ fn multiiter_rw() -> PyResult<()> {
         let arr1 = PyArray::from_array(py, &data1);
         let arr2 = PyArray::from_array(py, &data2);
         let iter = NpyMultiIterBuilder::new()
-            .add_readonly(arr1.readonly())
+            .add_readonly(arr1.readonly().unwrap())
             .add_readwrite(arr2)
             .build()?;
 
@@ -81,8 +84,8 @@ fn multiiter_rw() -> PyResult<()> {
         }
 
         let iter = NpyMultiIterBuilder::new()
-            .add_readonly(arr1.readonly())
-            .add_readonly(arr2.readonly())
+            .add_readonly(arr1.readonly().unwrap())
+            .add_readonly(arr2.readonly().unwrap())
             .build()?;
 
         for (x, y) in iter {
@@ -92,3 +95,543 @@ fn multiiter_rw() -> PyResult<()> {
         Ok(())
     })
 }
+
+#[test]
+fn chunked_iter() -> PyResult<()> {
+    let data = array![[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]];
+    pyo3::Python::with_gil(|py| {
+        let arr = PyArray::from_array(py, &data);
+        let iter = NpySingleIterBuilder::readwrite(arr).build_chunked()?;
+        for mut chunk in iter {
+            chunk.mapv_inplace(|x| x * 2.0);
+        }
+
+        let iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap()).build_chunked()?;
+        let (count, sum) = iter.fold((0, 0.0), |(count, sum), chunk| {
+            (count + chunk.len(), sum + chunk.sum())
+        });
+        assert_eq!(count, 6);
+        assert_approx_eq!(sum, 30.0);
+        Ok(())
+    })
+}
+
+#[test]
+fn chunked_iter_negative_strides() -> PyResult<()> {
+    let data = array![0.0, 1.0, 2.0, 3.0];
+    pyo3::Python::with_gil(|py| {
+        let arr = PyArray::from_array(py, &data);
+        let locals = [("arr", arr)].into_py_dict(py);
+        let arr: &PyArray1<f64> = py.eval("arr[::-1]", None, Some(locals))?.downcast()?;
+        let iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap())
+            .set(NpyIterFlag::DontNegateStrides)
+            .build_chunked()?;
+        let chunks = iter.map(|chunk| chunk.to_vec()).collect::<Vec<_>>();
+        assert_eq!(chunks.concat(), [3.0, 2.0, 1.0, 0.0]);
+        Ok(())
+    })
+}
+
+#[test]
+fn chunked_iter_rejects_unsupported_strides() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let locals = [("np", numpy::get_array_module(py)?)].into_py_dict(py);
+
+        let repeated: &PyArray1<f64> = py
+            .eval(
+                "np.lib.stride_tricks.as_strided(np.zeros(1), shape=(3,), strides=(0,), writeable=True)",
+                None,
+                Some(locals),
+            )?
+            .downcast()?;
+        assert!(NpySingleIterBuilder::readwrite(repeated)
+            .build_chunked()
+            .is_err());
+        assert!(NpySingleIterBuilder::readwrite(repeated).build().is_err());
+        assert!(NpySingleIterBuilder::readonly(repeated.readonly().unwrap())
+            .build_chunked()
+            .is_ok());
+
+        let field: &PyArray1<Complex32> = py
+            .eval(
+                "np.zeros(3, dtype=[('a', 'c8'), ('b', 'f4')])['a']",
+                None,
+                Some(locals),
+            )?
+            .downcast()?;
+        assert!(NpySingleIterBuilder::readonly(field.readonly().unwrap())
+            .build_chunked()
+            .is_err());
+        assert_eq!(
+            NpySingleIterBuilder::readonly(field.readonly().unwrap())
+                .build()?
+                .count(),
+            3
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_rejects_overlapping_writable_operands() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let a = PyArray::arange(py, 0.0, 6.0, 1.0);
+        let result = NpyMultiIterBuilder::new()
+            .add_readwrite(a)
+            .add_readwrite(a)
+            .build();
+        assert!(result.is_err());
+
+        let locals = [("a", a)].into_py_dict(py);
+        let head: &PyArray1<f64> = py.eval("a[:4]", None, Some(locals))?.downcast()?;
+        let tail: &PyArray1<f64> = py.eval("a[2:]", None, Some(locals))?.downcast()?;
+        let result = NpyMultiIterBuilder::new()
+            .add_readonly(head.readonly()?)
+            .add_readwrite(tail)
+            .build();
+        assert!(result.is_err());
+
+        let front: &PyArray1<f64> = py.eval("a[:3]", None, Some(locals))?.downcast()?;
+        let back: &PyArray1<f64> = py.eval("a[3:]", None, Some(locals))?.downcast()?;
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(front.readonly()?)
+            .add_readwrite(back)
+            .build()?;
+        for (x, y) in iter {
+            *y += x;
+        }
+        assert_eq!(back.readonly()?.as_slice()?, &[3.0, 5.0, 7.0]);
+        Ok(())
+    })
+}
+
+#[test]
+fn chunked_multiiter() -> PyResult<()> {
+    let data1 = array![[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]];
+    let data2 = array![[0.0, 0.0], [0.0, 0.0], [0.0, 0.0]];
+
+    pyo3::Python::with_gil(|py| {
+        let arr1 = PyArray::from_array(py, &data1);
+        let arr2 = PyArray::from_array(py, &data2);
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(arr1.readonly().unwrap())
+            .add_readwrite(arr2)
+            .build_chunked()?;
+
+        for (x, mut y) in iter {
+            assert_eq!(x.len(), y.len());
+            y.assign(&(&x * 2.0));
+        }
+
+        assert_eq!(arr2.readonly().unwrap().as_array(), data1 * 2.0);
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_with_c_index() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let arr: &PyArray2<i64> = py
+            .eval(
+                "__import__('numpy').asfortranarray(__import__('numpy').arange(6).reshape(2, 3))",
+                None,
+                None,
+            )?
+            .downcast()?;
+        let iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap())
+            .set(NpyIterFlag::CIndex)
+            .build()?
+            .with_index()?;
+        let mut count = 0;
+        for (index, elem) in iter {
+            assert_eq!(index as i64, *elem);
+            count += 1;
+        }
+        assert_eq!(count, 6);
+
+        let iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap()).build()?;
+        assert!(iter.index().is_none());
+        assert!(iter.with_index().is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_with_multi_index() -> PyResult<()> {
+    let data1 = array![[0, 1, 2], [10, 11, 12]];
+    let data2 = array![[0, 0, 0], [0, 0, 0]];
+
+    pyo3::Python::with_gil(|py| {
+        let arr1 = PyArray::from_array(py, &data1);
+        let arr2 = PyArray::from_array(py, &data2);
+        let iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::MultiIndex)
+            .add_readonly(arr1.readonly().unwrap())
+            .add_readwrite(arr2)
+            .build()?
+            .with_multi_index()?;
+
+        for (index, (x, y)) in iter {
+            assert_eq!(*x, 10 * index[0] as i64 + index[1] as i64);
+            *y = index[1] as i64;
+        }
+        assert_eq!(
+            arr2.readonly().unwrap().as_array(),
+            array![[0, 1, 2], [0, 1, 2]]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_goto_index() -> PyResult<()> {
+    let data = array![[0, 1, 2], [10, 11, 12]];
+
+    pyo3::Python::with_gil(|py| {
+        let arr = PyArray::from_array(py, &data);
+        let mut iter = NpySingleIterBuilder::readonly(arr.readonly().unwrap())
+            .set(NpyIterFlag::MultiIndex)
+            .set(NpyIterFlag::CIndex)
+            .build()?;
+
+        iter.goto_multi_index(&[1, 1])?;
+        assert_eq!(iter.next(), Some(&11));
+        assert_eq!(iter.multi_index(), Some(IxDyn(&[1, 1])));
+        assert_eq!(iter.index(), Some(4));
+
+        iter.goto_index(2)?;
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&10));
+
+        assert!(iter.goto_multi_index(&[1]).is_err());
+        assert!(iter.goto_multi_index(&[2, 0]).is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_heterogeneous() -> PyResult<()> {
+    let values = array![[0.5, 1.5], [2.5, 3.5]];
+    let mask = array![[true, false], [false, true]];
+    let indices = array![[0_i64, 0], [0, 0]];
+
+    pyo3::Python::with_gil(|py| {
+        let values = PyArray::from_array(py, &values);
+        let mask = PyArray::from_array(py, &mask);
+        let indices = PyArray::from_array(py, &indices);
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(values.readonly().unwrap())
+            .add_readonly(mask.readonly().unwrap())
+            .add_readwrite(indices)
+            .build()?;
+
+        for (value, mask, index) in iter {
+            let _: (&f64, &bool, &mut i64) = (value, mask, index);
+            if *mask {
+                *index = value.floor() as i64;
+            } else {
+                *index = -1;
+            }
+        }
+        assert_eq!(
+            indices.readonly().unwrap().as_array(),
+            array![[0, -1], [-1, 3]]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_output() -> PyResult<()> {
+    let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let b = array![10.0, 20.0, 30.0];
+
+    pyo3::Python::with_gil(|py| {
+        let a = PyArray::from_array(py, &a);
+        let b = PyArray::from_array(py, &b);
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(a.readonly().unwrap())
+            .add_readonly(b.readonly().unwrap())
+            .add_output::<f64>()
+            .add_output::<bool>()
+            .build()?;
+        let sum: &PyArray2<f64> = iter.operand(2)?;
+        let greater: &PyArray2<bool> = iter.operand(3)?;
+        assert!(iter.operand::<f64, IxDyn>(4).is_err());
+        assert!(iter.operand::<i32, IxDyn>(2).is_err());
+
+        for (a, b, sum, greater) in iter {
+            *sum = a + b;
+            *greater = a > &2.5;
+        }
+        assert_eq!(
+            sum.readonly().unwrap().as_array(),
+            array![[11.0, 22.0, 33.0], [14.0, 25.0, 36.0]]
+        );
+        assert_eq!(
+            greater.readonly().unwrap().as_array(),
+            array![[false, false, true], [true, true, true]]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_eight_operands() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let x = PyArray::arange(py, 0_i64, 4, 1);
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(x.readonly().unwrap())
+            .add_readonly(x.readonly().unwrap())
+            .add_readonly(x.readonly().unwrap())
+            .add_readonly(x.readonly().unwrap())
+            .add_readonly(x.readonly().unwrap())
+            .add_readonly(x.readonly().unwrap())
+            .add_readonly(x.readonly().unwrap())
+            .add_output::<i64>()
+            .build()?;
+        let out: &PyArray1<i64> = iter.operand(7)?;
+        for (a, b, c, d, e, f, g, out) in iter {
+            *out = a + b + c + d + e + f + g;
+        }
+        assert_eq!(out.readonly().unwrap().as_slice()?, &[0, 7, 14, 21]);
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_split() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let x = PyArray::arange(py, 0_i64, 10, 1);
+        let y = PyArray::<i64, _>::zeros(py, 10, false);
+        let mut iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::Ranged)
+            .add_readonly(x.readonly().unwrap())
+            .add_readwrite(y)
+            .build()?;
+        assert!(!iter.needs_api());
+
+        // The first item was already returned and is not part of any split.
+        let (first, _) = iter.next().unwrap();
+        assert_eq!(*first, 0);
+        let parts = iter.split(4)?;
+        let lens = parts
+            .iter()
+            .map(|part| part.size_hint().0)
+            .collect::<Vec<_>>();
+        assert_eq!(lens, [3, 2, 2, 2]);
+
+        let firsts = parts
+            .into_iter()
+            .map(|part| {
+                let mut firsts = Vec::new();
+                for (x, y) in part {
+                    if firsts.is_empty() {
+                        firsts.push(*x);
+                    }
+                    *y = x + 1;
+                }
+                firsts
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(firsts.concat(), [1, 4, 6, 8]);
+        assert_eq!(
+            y.readonly().unwrap().as_slice()?,
+            &[0, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn split_requires_ranged() {
+    pyo3::Python::with_gil(|py| {
+        let x = PyArray::arange(py, 0_i64, 10, 1);
+        let iter = NpySingleIterBuilder::readonly(x.readonly().unwrap())
+            .build()
+            .unwrap();
+        assert!(iter.split(2).is_err());
+        pyo3::py_run!(py, x, "assert x.flags['WRITEABLE']");
+    })
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn multiiter_par_iter() -> PyResult<()> {
+    use rayon::iter::ParallelIterator;
+
+    pyo3::Python::with_gil(|py| {
+        let x = PyArray::arange(py, 0_i64, 1000, 1);
+        let y = PyArray::<i64, _>::zeros(py, 1000, false);
+        let iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::Ranged)
+            .add_readonly(x.readonly().unwrap())
+            .add_readwrite(y)
+            .build()?
+            .into_par_iter()?;
+        let sum: i64 = py.allow_threads(|| {
+            iter.map(|(x, y)| {
+                *y = 2 * x;
+                *x
+            })
+            .sum()
+        });
+        assert_eq!(sum, 999 * 1000 / 2);
+        assert_eq!(y.readonly().unwrap().as_array().sum(), 999 * 1000);
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_cast() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let array = PyArray::arange(py, 0_i32, 10, 1);
+        let mut iter = NpySingleIterBuilder::readonly(array.readonly().unwrap())
+            .cast::<f64>()
+            .build_lending()?;
+        let mut sum = 0.0;
+        while let Some(x) = iter.next_item() {
+            sum += x / 2.0;
+        }
+        assert_approx_eq!(sum, 22.5);
+        drop(iter);
+
+        // Buffered items cannot outlive the next call to `next`.
+        let iter = NpySingleIterBuilder::readonly(array.readonly().unwrap())
+            .cast::<f64>()
+            .build();
+        assert!(iter.is_err());
+
+        // Casting to a smaller type is not safe.
+        let iter = NpySingleIterBuilder::readonly(array.readonly().unwrap())
+            .cast::<i8>()
+            .build_lending();
+        assert!(iter.is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_cast_readwrite() -> PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        let x = PyArray::arange(py, 0.0_f32, 4.0, 1.0);
+        let y = PyArray::arange(py, 0_i32, 4, 1);
+
+        // `f64` cannot be safely cast back to `i32`.
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+            .add_readwrite_as::<f64, _, _>(y)
+            .build_lending();
+        assert!(iter.is_err());
+
+        let mut iter = NpyMultiIterBuilder::new()
+            .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+            .add_readwrite_as::<f64, _, _>(y)
+            .casting(Casting::Unsafe)
+            .build_lending()?;
+        while let Some((x, y)) = iter.next_item() {
+            *y = 2.5 * x;
+        }
+        drop(iter);
+        assert_eq!(y.readonly().unwrap().as_slice()?, &[0, 2, 5, 7]);
+
+        let mut iter = NpyMultiIterBuilder::new()
+            .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+            .add_readwrite(y)
+            .build_lending_chunked()?;
+        while let Some((x, mut y)) = iter.next_chunk() {
+            y += &x.mapv(|x| x as i32);
+        }
+        drop(iter);
+        assert_eq!(y.readonly().unwrap().as_slice()?, &[0, 3, 7, 10]);
+
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly_as::<f64, _, _>(x.readonly().unwrap())
+            .add_readwrite(y)
+            .build_chunked();
+        assert!(iter.is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_reduce_op_axes() -> PyResult<()> {
+    let a = array![[[0.0, 1.0], [2.0, 3.0]], [[4.0, 5.0], [6.0, 7.0]]];
+
+    pyo3::Python::with_gil(|py| {
+        let a = PyArray::from_array(py, &a);
+        let mut iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::ReduceOk)
+            .add_readonly(a.readonly().unwrap())
+            .add_output::<f64>()
+            .add_output::<i64>()
+            .op_axes(1, &[None, Some(0), None])
+            .op_axes(2, &[None, Some(0), None])
+            .build_lending()?;
+        let sums: &PyArray1<f64> = iter.operand(1)?;
+        let counts: &PyArray1<i64> = iter.operand(2)?;
+
+        let mut first_visits = 0;
+        while iter.next_item().is_some() {
+            if iter.is_first_visit(1) {
+                first_visits += 1;
+            }
+            let (a, sum, count) = iter.current_item().unwrap();
+            *sum += a;
+            *count += 1;
+        }
+        assert_eq!(first_visits, 2);
+        drop(iter);
+        assert_eq!(sums.readonly().unwrap().as_slice()?, &[10.0, 18.0]);
+        assert_eq!(counts.readonly().unwrap().as_slice()?, &[4, 4]);
+        Ok(())
+    })
+}
+
+#[test]
+fn multiiter_op_axes_errors() {
+    pyo3::Python::with_gil(|py| {
+        let a = PyArray::arange(py, 0.0, 6.0, 1.0).reshape([2, 3]).unwrap();
+
+        // Reductions require `ReduceOk`.
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(a.readonly().unwrap())
+            .add_output::<f64>()
+            .op_axes(1, &[Some(0), None])
+            .build_lending();
+        assert!(iter.is_err());
+
+        // Reduced elements are visited repeatedly and hence cannot be returned by `Iterator`.
+        let iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::ReduceOk)
+            .add_readonly(a.readonly().unwrap())
+            .add_output::<f64>()
+            .op_axes(1, &[Some(0), None])
+            .build();
+        assert!(iter.is_err());
+        let iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::ReduceOk)
+            .add_readonly(a.readonly().unwrap())
+            .add_output::<f64>()
+            .op_axes(1, &[Some(0), None])
+            .build_lending_chunked();
+        assert!(iter.is_err());
+
+        // The numbers of iteration axes differ.
+        let iter = NpyMultiIterBuilder::new()
+            .set(NpyIterFlag::ReduceOk)
+            .add_readonly(a.readonly().unwrap())
+            .add_output::<f64>()
+            .op_axes(0, &[Some(0), Some(1)])
+            .op_axes(1, &[Some(0)])
+            .build_lending();
+        assert!(iter.is_err());
+
+        let iter = NpyMultiIterBuilder::new()
+            .add_readonly(a.readonly().unwrap())
+            .add_output::<f64>()
+            .op_axes(2, &[Some(0), Some(1)])
+            .build();
+        assert!(iter.is_err());
+    })
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "memmap")]
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use memmap2::{Mmap, MmapMut};
+use ndarray::array;
+use numpy::{PyArray1, PyArray2};
+use pyo3::{py_run, Python};
+
+/// A file in the temporary directory which is removed when dropped.
+struct TempFile(PathBuf);
+
+impl AsRef<Path> for TempFile {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // Removal can fail on platforms which do not allow removing mapped files.
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn temp_file(name: &str, values: &[f64]) -> TempFile {
+    // Include the process id so that concurrent test runs do not share files.
+    let path = std::env::temp_dir().join(format!("{}-{}.bin", name, std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    for value in values {
+        file.write_all(&value.to_ne_bytes()).unwrap();
+    }
+    TempFile(path)
+}
+
+#[test]
+fn from_mmap_readonly() {
+    let path = temp_file("numpy-from-mmap-readonly", &[0.0, 1.0, 2.0, 3.0, 4.0]);
+    let file = std::fs::File::open(&path).unwrap();
+    let mmap = unsafe { Mmap::map(&file).unwrap() };
+
+    Python::with_gil(|py| {
+        let arr = PyArray2::<f64>::from_mmap(py, mmap, [2, 2], 8).unwrap();
+        assert_eq!(
+            arr.readonly().unwrap().as_array(),
+            array![[1.0, 2.0], [3.0, 4.0]]
+        );
+        py_run!(py, arr, "assert not arr.flags['WRITEABLE']");
+    });
+}
+
+#[test]
+fn from_mmap_writeable() {
+    let path = temp_file("numpy-from-mmap-writeable", &[0.0, 1.0, 2.0]);
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .unwrap();
+    let mmap = unsafe { MmapMut::map_mut(&file).unwrap() };
+
+    Python::with_gil(|py| {
+        let arr = PyArray1::<f64>::from_mmap(py, mmap, [3], 0).unwrap();
+        py_run!(py, arr, "arr[1] = 42.0");
+        assert_eq!(
+            arr.readonly().unwrap().as_slice().unwrap(),
+            &[0.0, 42.0, 2.0]
+        );
+    });
+
+    let file = std::fs::File::open(&path).unwrap();
+    let mmap = unsafe { Mmap::map(&file).unwrap() };
+    assert_eq!(&mmap[8..16], &42.0_f64.to_ne_bytes());
+}
+
+#[test]
+fn from_mmap_checks_length_and_alignment() {
+    let path = temp_file("numpy-from-mmap-checks", &[0.0, 1.0, 2.0]);
+    let file = std::fs::File::open(&path).unwrap();
+
+    Python::with_gil(|py| {
+        let mmap = unsafe { Mmap::map(&file).unwrap() };
+        assert!(PyArray1::<f64>::from_mmap(py, mmap, [4], 0).is_err());
+
+        let mmap = unsafe { Mmap::map(&file).unwrap() };
+        assert!(PyArray1::<f64>::from_mmap(py, mmap, [1], 20).is_err());
+
+        let mmap = unsafe { Mmap::map(&file).unwrap() };
+        assert!(PyArray1::<f64>::from_mmap(py, mmap, [1], 3).is_err());
+    });
+}
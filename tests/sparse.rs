@@ -0,0 +1,119 @@
+use ndarray::*;
+use numpy::sparse::{PyCooMatrix, PyCscMatrix, PyCsrMatrix};
+use pyo3::{
+    prelude::*,
+    types::{IntoPyDict, PyDict},
+};
+
+fn scipy_locals(py: Python) -> &PyDict {
+    let locals = PyDict::new(py);
+    py.run(
+        "import numpy as np; import scipy.sparse as sp",
+        None,
+        Some(locals),
+    )
+    .unwrap();
+    locals
+}
+
+#[test]
+fn extract_csr() {
+    Python::with_gil(|py| {
+        let locals = scipy_locals(py);
+        let matrix = py
+            .eval(
+                "sp.csr_matrix(np.array([[1.0, 0.0, 2.0], [0.0, 0.0, 0.0], [0.0, 3.0, 0.0]]))",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+
+        let csr: PyCsrMatrix<f64> = matrix.extract().unwrap();
+        assert_eq!(csr.shape(), (3, 3));
+        assert_eq!(csr.nnz(), 3);
+        assert_eq!(csr.data().as_array(), array![1.0, 2.0, 3.0]);
+        assert_eq!(csr.indices().as_array(), array![0, 2, 1]);
+        assert_eq!(csr.indptr().as_array(), array![0, 2, 2, 3]);
+        assert_eq!(csr.row(0), (&[0, 2][..], &[1.0, 2.0][..]));
+        assert!(csr.row(1).0.is_empty());
+
+        assert!(matrix.extract::<PyCsrMatrix<f32>>().is_err());
+        assert!(matrix.extract::<PyCsrMatrix<f64, i64>>().is_err());
+        assert!(matrix.extract::<PyCscMatrix<f64>>().is_err());
+
+        let csc: PyCscMatrix<f64> = py
+            .eval("m.tocsc()", None, Some([("m", matrix)].into_py_dict(py)))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(csc.column(1), (&[2][..], &[3.0][..]));
+    })
+}
+
+#[test]
+fn extract_malformed() {
+    Python::with_gil(|py| {
+        let locals = scipy_locals(py);
+        let matrix = py
+            .eval("sp.csr_matrix((2, 2), dtype='float64')", None, Some(locals))
+            .unwrap();
+        matrix
+            .setattr(
+                "indptr",
+                py.eval("np.array([0, 1, 0], dtype='int32')", None, Some(locals))
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(matrix.extract::<PyCsrMatrix<f64>>().is_err());
+
+        let strided = py
+            .eval("sp.csr_matrix(np.eye(2))", None, Some(locals))
+            .unwrap();
+        strided
+            .setattr(
+                "data",
+                py.eval("np.ones(4)[::2]", None, Some(locals)).unwrap(),
+            )
+            .unwrap();
+        let err = strided.extract::<PyCsrMatrix<f64>>().err().unwrap();
+        assert!(err.to_string().contains("data is not contiguous"));
+
+        let plain = py.eval("np.eye(2)", None, Some(locals)).unwrap();
+        assert!(plain.extract::<PyCsrMatrix<f64>>().is_err());
+    })
+}
+
+#[test]
+fn create_and_extract_coo() {
+    Python::with_gil(|py| {
+        let matrix =
+            PyCooMatrix::<i64>::create(py, (2, 2), vec![5, 7], vec![0, 1], vec![1, 0]).unwrap();
+        pyo3::py_run!(
+            py,
+            matrix,
+            "assert matrix.format == 'coo' and matrix.toarray().tolist() == [[0, 5], [7, 0]]"
+        );
+
+        let coo: PyCooMatrix<i64> = matrix.extract().unwrap();
+        assert_eq!(coo.shape(), (2, 2));
+        assert_eq!(coo.iter().collect::<Vec<_>>(), [(0, 1, &5), (1, 0, &7)]);
+    })
+}
+
+#[test]
+fn create_compressed() {
+    Python::with_gil(|py| {
+        let matrix =
+            PyCscMatrix::<f32>::create(py, (2, 2), vec![1.0, 2.0], vec![1, 0], vec![0, 1, 2])
+                .unwrap();
+        pyo3::py_run!(
+            py,
+            matrix,
+            "assert matrix.format == 'csc' and matrix.toarray().tolist() == [[0, 2], [1, 0]]"
+        );
+
+        assert!(
+            PyCsrMatrix::<f32>::create(py, (2, 2), vec![1.0], vec![0, 1], vec![0, 1, 2]).is_err()
+        );
+    })
+}
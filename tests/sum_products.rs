@@ -1,4 +1,8 @@
-use numpy::{array, dot, einsum, inner, pyarray, PyArray1, PyArray2};
+use numpy::{
+    array, dot, einsum, inner, kron, matmul, matmul_into, outer, pyarray, tensordot, trace, vdot,
+    Casting, Complex64, Einsum, PyArray, PyArray0, PyArray1, PyArray2, PyArray3,
+};
+use pyo3::{PyObject, ToPyObject};
 
 #[test]
 fn test_dot() {
@@ -6,7 +10,7 @@ fn test_dot() {
         let a = pyarray![py, [1, 0], [0, 1]];
         let b = pyarray![py, [4, 1], [2, 2]];
         let c = dot(a, b).unwrap();
-        assert_eq!(c.readonly().as_array(), array![[4, 1], [2, 2]]);
+        assert_eq!(c.readonly().unwrap().as_array(), array![[4, 1], [2, 2]]);
         let a = pyarray![py, 1, 2, 3];
         let err: pyo3::PyResult<&PyArray2<_>> = dot(a, b);
         let err = err.unwrap_err();
@@ -20,11 +24,11 @@ fn test_inner() {
         let a = pyarray![py, 1, 2, 3];
         let b = pyarray![py, 0, 1, 0];
         let c = inner(a, b).unwrap();
-        assert_eq!(c.readonly().as_array(), ndarray::arr0(2));
+        assert_eq!(c.readonly().unwrap().as_array(), ndarray::arr0(2));
         let a = pyarray![py, [1, 0], [0, 1]];
         let b = pyarray![py, [4, 1], [2, 2]];
         let c = inner(a, b).unwrap();
-        assert_eq!(c.readonly().as_array(), array![[4, 2], [1, 2]]);
+        assert_eq!(c.readonly().unwrap().as_array(), array![[4, 2], [1, 2]]);
         let a = pyarray![py, 1, 2, 3];
         let err: pyo3::PyResult<&PyArray2<_>> = inner(a, b);
         let err = err.unwrap_err();
@@ -41,24 +45,162 @@ fn test_einsum() {
         let b = pyarray![py, 0, 1, 2, 3, 4];
         let c = pyarray![py, [0, 1, 2], [3, 4, 5]];
         assert_eq!(
-            einsum!("ii", a).unwrap().readonly().as_array(),
+            einsum!("ii", a).unwrap().readonly().unwrap().as_array(),
             ndarray::arr0(60)
         );
         assert_eq!(
-            einsum!("ii->i", a).unwrap().readonly().as_array(),
+            einsum!("ii->i", a).unwrap().readonly().unwrap().as_array(),
             array![0, 6, 12, 18, 24],
         );
         assert_eq!(
-            einsum!("ij->i", a).unwrap().readonly().as_array(),
+            einsum!("ij->i", a).unwrap().readonly().unwrap().as_array(),
             array![10, 35, 60, 85, 110],
         );
         assert_eq!(
-            einsum!("ji", c).unwrap().readonly().as_array(),
+            einsum!("ji", c).unwrap().readonly().unwrap().as_array(),
             array![[0, 3], [1, 4], [2, 5]],
         );
         assert_eq!(
-            einsum!("ij,j", a, b).unwrap().readonly().as_array(),
+            einsum!("ij,j", a, b)
+                .unwrap()
+                .readonly()
+                .unwrap()
+                .as_array(),
             array![30, 80, 130, 180, 230],
         );
     })
 }
+
+#[test]
+fn test_einsum_builder() {
+    pyo3::Python::with_gil(|py| {
+        let a = PyArray::arange(py, 0.0, 6.0, 1.0).reshape([2, 3]).unwrap();
+        let b = pyarray![py, 1_i32, 2, 3].to_dyn();
+
+        let subscripts = ["ij", "j"].join(",") + "->i";
+        let c: &PyArray1<f64> = Einsum::new(&subscripts)
+            .operand(a)
+            .operand(b)
+            .call()
+            .unwrap();
+        assert_eq!(c.readonly().unwrap().as_array(), array![8.0, 26.0]);
+
+        let out = PyArray2::<f64>::zeros(py, [3, 2], true);
+        let transposed: &PyArray2<f64> = Einsum::new("ij->ji").operand(a).out(out).call().unwrap();
+        assert_eq!(
+            out.readonly().unwrap().as_array(),
+            array![[0.0, 3.0], [1.0, 4.0], [2.0, 5.0]]
+        );
+        pyo3::py_run!(py, out transposed, "assert out is transposed");
+
+        let no_cast: pyo3::PyResult<&PyArray1<f64>> = Einsum::new("ij,j->i")
+            .operand(a)
+            .operand(b)
+            .casting(Casting::No)
+            .call();
+        assert!(no_cast.is_err());
+
+        for subscripts in &["ij,j\0", "ij->i->j", "ij", "ij,j;"] {
+            let err = Einsum::new(subscripts)
+                .operand(a)
+                .operand(b)
+                .call::<f64, ndarray::Ix1>()
+                .unwrap_err();
+            assert!(err.is_instance::<pyo3::exceptions::PyValueError>(py));
+        }
+    })
+}
+
+#[test]
+fn test_matmul() {
+    pyo3::Python::with_gil(|py| {
+        let a = pyarray![py, [1.0, 2.0], [3.0, 4.0]];
+        let v = pyarray![py, 1.0, 1.0];
+        let av: &PyArray1<f64> = matmul(a, v).unwrap();
+        assert_eq!(av.readonly().unwrap().as_array(), array![3.0, 7.0]);
+        let vv: &PyArray0<f64> = matmul(v, v).unwrap();
+        assert_eq!(vv.item(), 2.0);
+
+        let stack = PyArray::arange(py, 0.0, 8.0, 1.0)
+            .reshape([2, 2, 2])
+            .unwrap();
+        let out = PyArray3::<f64>::zeros(py, [2, 2, 2], false);
+        matmul_into(stack, a, out).unwrap();
+        assert_eq!(
+            out.readonly().unwrap().as_array(),
+            array![[[3.0, 4.0], [11.0, 16.0]], [[19.0, 28.0], [27.0, 40.0]]]
+        );
+
+        let out = PyArray1::<f64>::zeros(py, [2], false);
+        matmul_into(a, v, out).unwrap();
+        assert_eq!(out.readonly().unwrap().as_array(), array![3.0, 7.0]);
+
+        let err = matmul(a, pyarray![py, 1.0, 2.0, 3.0]).unwrap_err();
+        assert!(err.to_string().contains("not aligned"), "{}", err);
+        let err = matmul(stack, pyarray![py, 1.0, 2.0, 3.0]).unwrap_err();
+        assert!(err.to_string().contains("mismatch"), "{}", err);
+
+        let scalar = PyArray::from_vec(py, vec![2.0])
+            .reshape(())
+            .unwrap()
+            .to_dyn();
+        assert!(matmul(scalar, a.to_dyn()).is_err());
+        assert!(matmul(a.to_dyn(), scalar).is_err());
+
+        let objects = PyArray::from_vec(py, vec![1.to_object(py), 2.to_object(py)]);
+        let dot: &PyArray0<PyObject> = matmul(objects, objects).unwrap();
+        assert_eq!(
+            dot.readonly().unwrap().as_array()[()]
+                .extract::<i64>(py)
+                .unwrap(),
+            5
+        );
+    })
+}
+
+#[test]
+fn test_tensordot_outer_kron() {
+    pyo3::Python::with_gil(|py| {
+        let a = pyarray![py, [1, 2], [3, 4]];
+        let b = pyarray![py, [5, 6], [7, 8]];
+        let full: &PyArray0<i32> = tensordot(a, b, (&[0, 1], &[0, 1])).unwrap();
+        assert_eq!(full.item(), 70);
+        let product: &PyArray2<i32> = tensordot(a, b, (&[1], &[0])).unwrap();
+        assert_eq!(
+            product.readonly().unwrap().as_array(),
+            array![[19, 22], [43, 50]]
+        );
+        assert!(tensordot::<_, _, _, ndarray::Ix2>(a, b, (&[1], &[0, 1])).is_err());
+
+        let c = outer(a, pyarray![py, 1, -1]).unwrap();
+        assert_eq!(
+            c.readonly().unwrap().as_array(),
+            array![[1, -1], [2, -2], [3, -3], [4, -4]]
+        );
+
+        let k = kron(pyarray![py, 1, 10], pyarray![py, 1, 2, 3]).unwrap();
+        assert_eq!(
+            k.readonly().unwrap().as_slice().unwrap(),
+            &[1, 2, 3, 10, 20, 30]
+        );
+    })
+}
+
+#[test]
+fn test_vdot_trace() {
+    pyo3::Python::with_gil(|py| {
+        let a = pyarray![py, [1, 2], [3, 4]];
+        assert_eq!(vdot(a, a).unwrap(), 30);
+
+        let z = pyarray![py, Complex64::new(0.0, 1.0)];
+        assert_eq!(vdot(z, z).unwrap(), Complex64::new(1.0, 0.0));
+
+        assert_eq!(trace(a, 0, 0, 1).unwrap().item(), 5);
+        let stack = PyArray::arange(py, 0, 8, 1).reshape([2, 2, 2]).unwrap();
+        let traces = trace(stack, 0, 1, 2).unwrap();
+        assert_eq!(traces.readonly().unwrap().as_slice().unwrap(), &[3, 11]);
+
+        assert!(trace(a, isize::MAX, 0, 1).is_err());
+        assert!(trace(a, 0, usize::MAX, 1).is_err());
+    })
+}
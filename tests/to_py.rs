@@ -5,7 +5,7 @@ use numpy::*;
 fn to_pyarray_vec() {
     pyo3::Python::with_gil(|py| {
         let a = vec![1, 2, 3];
-        let arr = a.to_pyarray(py).readonly();
+        let arr = a.to_pyarray(py).readonly().unwrap();
         println!("arr.shape = {:?}", arr.shape());
         assert_eq!(arr.shape(), [3]);
         assert_eq!(arr.as_slice().unwrap(), &[1, 2, 3])
@@ -31,7 +31,9 @@ fn to_pyarray_array() {
 #[test]
 fn iter_to_pyarray() {
     pyo3::Python::with_gil(|py| {
-        let arr = PyArray::from_iter(py, (0..10).map(|x| x * x)).readonly();
+        let arr = PyArray::from_iter(py, (0..10).map(|x| x * x))
+            .readonly()
+            .unwrap();
         assert_eq!(
             arr.as_slice().unwrap(),
             &[0, 1, 4, 9, 16, 25, 36, 49, 64, 81]
@@ -42,7 +44,7 @@ fn iter_to_pyarray() {
 #[test]
 fn long_iter_to_pyarray() {
     pyo3::Python::with_gil(|py| {
-        let arr = PyArray::from_iter(py, 0u32..512).readonly();
+        let arr = PyArray::from_iter(py, 0u32..512).readonly().unwrap();
         let slice = arr.as_slice().unwrap();
         for (i, &elem) in slice.iter().enumerate() {
             assert_eq!(i as u32, elem);
@@ -57,7 +59,7 @@ macro_rules! small_array_test {
             $({
                 pyo3::Python::with_gil(|py| {
                     let array: [$t; 2] = [$t::min_value(), $t::max_value()];
-                    let pyarray = array.to_pyarray(py).readonly();
+                    let pyarray = array.to_pyarray(py).readonly().unwrap();
                     assert_eq!(
                         pyarray.as_slice().unwrap(),
                         &[$t::min_value(), $t::max_value()]
@@ -89,7 +91,7 @@ fn usize_dtype() {
 fn into_pyarray_vec() {
     let a = vec![1, 2, 3];
     pyo3::Python::with_gil(|py| {
-        let arr = a.into_pyarray(py).readonly();
+        let arr = a.into_pyarray(py).readonly().unwrap();
         assert_eq!(arr.as_slice().unwrap(), &[1, 2, 3])
     })
 }
@@ -134,7 +136,7 @@ fn into_pyarray_collapsed() {
     let copy = arr.clone();
     pyo3::Python::with_gil(|py| {
         let py_arr = arr.into_pyarray(py);
-        assert_eq!(py_arr.readonly().as_array(), copy);
+        assert_eq!(py_arr.readonly().unwrap().as_array(), copy);
     })
 }
 
@@ -145,7 +147,7 @@ fn forder_to_pyarray() {
         let fortran_matrix = matrix.reversed_axes();
         let fmat_py = fortran_matrix.to_pyarray(py);
         assert_eq!(
-            fmat_py.readonly().as_array(),
+            fmat_py.readonly().unwrap().as_array(),
             array![[0, 2, 4, 6], [1, 3, 5, 7]],
         );
         pyo3::py_run!(py, fmat_py, "assert fmat_py.flags['F_CONTIGUOUS']")
@@ -159,7 +161,7 @@ fn slice_to_pyarray() {
         let slice = matrix.slice(s![1..4; -1, ..]);
         let slice_py = slice.to_pyarray(py);
         assert_eq!(
-            slice_py.readonly().as_array(),
+            slice_py.readonly().unwrap().as_array(),
             array![[6, 7], [4, 5], [2, 3]],
         );
         pyo3::py_run!(py, slice_py, "assert slice_py.flags['C_CONTIGUOUS']")
@@ -173,7 +175,7 @@ fn forder_into_pyarray() {
         let fortran_matrix = matrix.reversed_axes();
         let fmat_py = fortran_matrix.into_pyarray(py);
         assert_eq!(
-            fmat_py.readonly().as_array(),
+            fmat_py.readonly().unwrap().as_array(),
             array![[0, 2, 4, 6], [1, 3, 5, 7]],
         );
         pyo3::py_run!(py, fmat_py, "assert fmat_py.flags['F_CONTIGUOUS']")
@@ -192,7 +194,7 @@ fn to_pyarray_object_vec() {
         let dict = PyDict::new(py);
         let string = PyString::new(py, "Hello:)");
         let vec = vec![dict.to_object(py), string.to_object(py)];
-        let arr = vec.to_pyarray(py).readonly();
+        let arr = vec.to_pyarray(py).readonly().unwrap();
 
         for (a, b) in vec.iter().zip(arr.as_slice().unwrap().iter()) {
             assert_eq!(
@@ -217,7 +219,7 @@ fn to_pyarray_object_array() {
         nd_arr[(0, 2)] = PyDict::new(py).to_object(py);
         nd_arr[(1, 0)] = PyString::new(py, "Hello:)").to_object(py);
 
-        let py_arr = nd_arr.to_pyarray(py).readonly();
+        let py_arr = nd_arr.to_pyarray(py).readonly().unwrap();
 
         for (a, b) in nd_arr
             .as_slice()
@@ -256,3 +258,53 @@ fn slice_box_type_confusion() {
         let _arr = vec.into_pyarray(py);
     });
 }
+
+#[test]
+fn into_pyarray_arc_slice() {
+    use std::sync::Arc;
+
+    let data: Arc<[f64]> = Arc::from(vec![1.0, 2.0, 3.0]);
+    pyo3::Python::with_gil(|py| {
+        let arr = data.clone().into_pyarray(py);
+        assert_eq!(
+            arr.readonly().unwrap().as_slice().unwrap(),
+            &[1.0, 2.0, 3.0]
+        );
+        assert_eq!(unsafe { arr.as_slice() }.unwrap().as_ptr(), data.as_ptr());
+        pyo3::py_run!(py, arr, "assert not arr.flags['WRITEABLE']");
+    });
+}
+
+#[test]
+fn into_pyarray_arc_vec() {
+    use std::sync::Arc;
+
+    let data = Arc::new(vec![1, 2, 3]);
+    pyo3::Python::with_gil(|py| {
+        let arr = data.clone().into_pyarray(py);
+        assert_eq!(arr.readonly().unwrap().as_slice().unwrap(), &[1, 2, 3]);
+        pyo3::py_run!(
+            py,
+            arr,
+            r#"
+try:
+    arr[1] = 4
+except ValueError:
+    pass
+else:
+    assert False
+"#
+        );
+    });
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn into_pyarray_bytes() {
+    let data = bytes::Bytes::from_static(b"abc");
+    pyo3::Python::with_gil(|py| {
+        let arr = data.into_pyarray(py);
+        assert_eq!(arr.readonly().unwrap().as_slice().unwrap(), b"abc");
+        pyo3::py_run!(py, arr, "assert not arr.flags['WRITEABLE']");
+    });
+}